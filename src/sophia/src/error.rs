@@ -9,11 +9,46 @@ use std::fmt;
 #[derive(Debug)]
 pub enum Error {
     Save(String),
-    Load(String),
+    Load(LoadError),
+    Build(String),
     POSPrediction(String),
     Generic(String),
 }
 
+/// Reasons a vocabulary data store or cache failed to load.
+#[derive(Debug)]
+pub enum LoadError {
+    NotFound(String),
+    Corrupt(String),
+    VersionMismatch {
+        expected: i8,
+        found: (i8, i8, i8),
+    },
+    HashMismatch {
+        expected: String,
+        found: String,
+    },
+    FormatMismatch {
+        expected: u32,
+        found: u32,
+    },
+    SectionHashMismatch {
+        section: String,
+        expected: String,
+        found: String,
+    },
+    Incompatible {
+        section: String,
+        writer_version: String,
+        reason: String,
+    },
+    MissingHash,
+    MissingSignature,
+    InvalidSignature,
+    InvalidKey(String),
+    Parse(String),
+}
+
 impl std::error::Error for Error {}
 
 impl fmt::Display for Error {
@@ -21,12 +56,85 @@ impl fmt::Display for Error {
         match self {
             Error::Save(err) => write!(f, "Save error: {}", err),
             Error::Load(err) => write!(f, "Load error: {}", err),
+            Error::Build(err) => write!(f, "Build error: {}", err),
             Error::POSPrediction(err) => write!(f, "POS tagger logistic regression error: {}", err),
             Error::Generic(msg) => write!(f, "{}", msg),
         }
     }
 }
 
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::NotFound(filename) => {
+                write!(f, "No vocabulary file exists at, {}", filename)
+            }
+            LoadError::Corrupt(msg) => write!(f, "{}", msg),
+            LoadError::VersionMismatch { expected, found } => write!(
+                f,
+                "Vocabulary data store version {}.{}.{} is incompatible, expected major version {}",
+                found.0, found.1, found.2, expected
+            ),
+            LoadError::HashMismatch { expected, found } => write!(
+                f,
+                "Vocabulary data store integrity check failed, expected SHA256 hash {} but found {}",
+                expected, found
+            ),
+            LoadError::FormatMismatch { expected, found } if found > expected => write!(
+                f,
+                "Vocabulary data store format version {} is newer than the supported format version {}, please upgrade Sophia to load it",
+                found, expected
+            ),
+            LoadError::FormatMismatch { expected, found } => write!(
+                f,
+                "Vocabulary data store format version {} is no longer supported, expected format version {}, please rebuild the data store",
+                found, expected
+            ),
+            LoadError::SectionHashMismatch {
+                section,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Vocabulary data store integrity check failed for the '{}' section, expected SHA256 hash {} but found {}",
+                section, expected, found
+            ),
+            LoadError::Incompatible {
+                section,
+                writer_version,
+                reason,
+            } => {
+                let writer = if writer_version.is_empty() {
+                    "an earlier version of Sophia".to_string()
+                } else {
+                    format!("Sophia v{}", writer_version)
+                };
+                write!(
+                    f,
+                    "Unable to read the '{}' section of the vocabulary data store written by {}, as it is incompatible with Sophia v{}, please rebuild or re-download the data store.  Error: {}",
+                    section,
+                    writer,
+                    env!("CARGO_PKG_VERSION"),
+                    reason
+                )
+            }
+            LoadError::MissingHash => write!(
+                f,
+                "Vocabulary data store is not sealed with a SHA256 hash, and unsealed data stores are not allowed"
+            ),
+            LoadError::MissingSignature => write!(f, "Vocabulary data store is not signed"),
+            LoadError::InvalidSignature => {
+                write!(
+                    f,
+                    "Vocabulary data store signature does not match the public key"
+                )
+            }
+            LoadError::InvalidKey(msg) => write!(f, "Invalid signing key, {}", msg),
+            LoadError::Parse(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
         Error::Generic(err.to_string())
@@ -4,12 +4,17 @@
 // License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
 // Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
 
-use super::{CoreferenceCategories, Interpretation, PhraseBuffer};
+use super::{
+    AntecedentBuffer, CoreferenceCategories, Interpretation, InterpretationStream, PhraseBuffer,
+};
 use crate::interpret::phrase::{Adjective, Adverb};
 use crate::pos_tagger::POSTag;
-use crate::tokenizer::{TokenizedInput, Tokenizer};
+use crate::tokenizer::{
+    SentenceSplitter, StreamUnit, Token, TokenizedInput, Tokenizer, TokenizerState,
+};
 use crate::vocab::VocabDatabase;
 use std::collections::HashMap;
+use std::io::BufRead;
 
 pub struct Interpreter {
     coref_categories: CoreferenceCategories,
@@ -30,10 +35,38 @@ impl Interpreter {
         input: &str,
         tokenizer: &Tokenizer,
         vocab: &VocabDatabase,
+    ) -> Interpretation {
+        let mut antecedents = self.antecedent_buffer();
+        self.interpret_with_state(
+            input,
+            tokenizer,
+            vocab,
+            &mut TokenizerState::default(),
+            &mut antecedents,
+        )
+    }
+
+    /// Interprets input continuing from the tokenizer state and antecedents left by the previous chunk, updating both for the next chunk.
+    pub fn interpret_with_state(
+        &self,
+        input: &str,
+        tokenizer: &Tokenizer,
+        vocab: &VocabDatabase,
+        state: &mut TokenizerState,
+        antecedents: &mut AntecedentBuffer,
     ) -> Interpretation {
         // Tokenize input
-        let mut tokens = tokenizer.encode(input, vocab);
+        let mut tokens = tokenizer.encode_with_state(input, vocab, state);
         let mut buffer = PhraseBuffer::new(&self.coref_categories, vocab);
+        buffer.antecedents = std::mem::take(antecedents);
+
+        // Get last position of each sentence, so abbreviations and initials do not split phrases
+        let mwe: Vec<Token> = tokens.mwe().collect();
+        let sentence_ends: Vec<usize> = SentenceSplitter::new()
+            .token_ranges(input, &mwe)
+            .iter()
+            .map(|sentence| sentence.end - 1)
+            .collect();
 
         // GO through tokens
         for (x, token) in tokens.mwe().enumerate() {
@@ -44,8 +77,10 @@ impl Interpreter {
                 buffer.add_intent(intent, length);
             }
 
-            if token.is_sentence_stopper() {
+            if token.is_sentence_stopper() && sentence_ends.contains(&x) {
                 buffer.hard_split(x);
+            } else if token.is_sentence_stopper() {
+                buffer.noise.push(x);
             } else if token.is_noun()
                 && buffer.last_pos == POSTag::VBG
                 && !buffer.current_verbs.is_empty()
@@ -98,8 +133,11 @@ impl Interpreter {
             buffer.last_pos = token.pos;
         }
 
-        // Finish buffer
-        buffer.hard_split(buffer.tokens.len() - 1);
+        // Finish buffer, unless the input contained no tokens (eg. a separator line)
+        if !buffer.tokens.is_empty() {
+            buffer.hard_split(buffer.tokens.len() - 1);
+        }
+        *antecedents = std::mem::take(&mut buffer.antecedents);
 
         // Instantiate interpretation
         Interpretation {
@@ -110,6 +148,22 @@ impl Interpreter {
         }
     }
 
+    /// Streams input from a buffered reader, interpreting one sentence or paragraph at a time with memory bounded to the current chunk.
+    pub fn interpret_stream<'a, R: BufRead>(
+        &'a self,
+        reader: R,
+        unit: StreamUnit,
+        tokenizer: &'a Tokenizer,
+        vocab: &'a VocabDatabase,
+    ) -> InterpretationStream<'a, R> {
+        InterpretationStream::new(reader, unit, self, tokenizer, vocab)
+    }
+
+    /// Creates an empty antecedent buffer, for carrying antecedents across chunks of streamed input.
+    pub fn antecedent_buffer(&self) -> AntecedentBuffer {
+        AntecedentBuffer::new(&self.coref_categories)
+    }
+
     /// Computes classification scores for tokens by averaging scores per code from multi-word expression scoring.
     /// Returns a HashMap mapping classification codes to their average scores.
     fn get_scores(&self, tokens: &TokenizedInput) -> HashMap<i8, f32> {
@@ -14,6 +14,7 @@ pub use self::phrase::{
     VerbModifier, VerbSibling,
 };
 pub use self::phrase_buffer::PhraseBuffer;
+pub use self::stream::InterpretationStream;
 
 mod antecedent_buffer;
 mod buffer;
@@ -22,3 +23,4 @@ mod interpretation;
 mod interpreter;
 mod phrase;
 mod phrase_buffer;
+mod stream;
@@ -181,6 +181,14 @@ impl Phrase {
         format!("{}{}", words.join(" "), split_word)
     }
 
+    /// Returns the byte range of the phrase within the original input, using the spans of the MWE tokens it covers.
+    pub fn span(&self, tokens: &[Token]) -> Range<usize> {
+        let covered = tokens.get(self.range.clone()).unwrap_or_default();
+        let start = covered.iter().map(|token| token.span.start).min().unwrap_or(0);
+        let end = covered.iter().map(|token| token.span.end).max().unwrap_or(start);
+        start..end
+    }
+
     /// Converts the phrase to a detailed debug string, including nouns and verbs, using the provided tokens.
     pub fn to_debug_string(&self, tokens: &[Token]) -> String {
         let mut lines = vec![self.to_string(tokens), String::new()];
@@ -0,0 +1,74 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+use super::{AntecedentBuffer, Interpretation, Interpreter};
+use crate::error::Error;
+use crate::tokenizer::{ChunkReader, StreamUnit, Tokenizer, TokenizerState};
+use crate::vocab::VocabDatabase;
+use std::io::BufRead;
+
+/// Streams interpretations from a buffered reader, one sentence or paragraph at a time.
+///
+/// Both the tokenization state and the antecedent buffer are carried across chunks, so pronouns are resolved against
+/// antecedents from earlier sentences of the stream.
+pub struct InterpretationStream<'a, R: BufRead> {
+    chunks: ChunkReader<R>,
+    interpreter: &'a Interpreter,
+    tokenizer: &'a Tokenizer,
+    vocab: &'a VocabDatabase,
+    state: TokenizerState,
+    antecedents: AntecedentBuffer,
+}
+
+impl<'a, R: BufRead> InterpretationStream<'a, R> {
+    /// Creates a new InterpretationStream over the reader, yielding the given unit of text.
+    pub fn new(
+        reader: R,
+        unit: StreamUnit,
+        interpreter: &'a Interpreter,
+        tokenizer: &'a Tokenizer,
+        vocab: &'a VocabDatabase,
+    ) -> Self {
+        Self {
+            chunks: ChunkReader::new(reader, unit),
+            interpreter,
+            tokenizer,
+            vocab,
+            state: TokenizerState::default(),
+            antecedents: interpreter.antecedent_buffer(),
+        }
+    }
+
+    /// Sets the maximum size in bytes of a single chunk, after which buffered input is interpreted even if incomplete.
+    pub fn max_chunk_size(mut self, size: usize) -> Self {
+        self.chunks.max_chunk_size = size.max(1);
+        self
+    }
+
+    /// Returns the byte offset within the stream of the most recently yielded chunk, which token spans are relative to.
+    pub fn offset(&self) -> usize {
+        self.chunks.offset()
+    }
+}
+
+impl<R: BufRead> Iterator for InterpretationStream<'_, R> {
+    type Item = Result<Interpretation, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = match self.chunks.next()? {
+            Ok(r) => r,
+            Err(e) => return Some(Err(e)),
+        };
+
+        Some(Ok(self.interpreter.interpret_with_state(
+            &chunk,
+            self.tokenizer,
+            self.vocab,
+            &mut self.state,
+            &mut self.antecedents,
+        )))
+    }
+}
@@ -6,12 +6,13 @@
 
 #![allow(non_camel_case_types)]
 
-pub use self::error::Error;
+pub use self::error::{Error, LoadError};
 pub use self::sophia::Sophia;
 
 pub mod error;
 pub mod interpret;
 pub mod pos_tagger;
+pub mod similarity;
 pub mod sophia;
 pub mod tokenizer;
 pub mod vocab;
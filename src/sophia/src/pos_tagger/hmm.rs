@@ -10,6 +10,7 @@ use crate::vocab::{Capitalization, VocabMWE};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::ops::Range;
 
 pub const TOTAL_TAGS: usize = 47;
 
@@ -45,26 +46,13 @@ where
         }
     }
 
-    /// Apply hmm model to vector of tokens
-    pub fn apply(&self, tokens: &mut [Token]) {
-        let mut start_pos = 0;
-        let mut end_pos: usize;
-        loop {
-            // Get end position
-            end_pos = match tokens[start_pos..].iter().position(|token| token.pos == POSTag::SS) {
-                Some(r) => r + start_pos + 1,
-                None => tokens.len().saturating_sub(1)
-            };
-            if start_pos >= end_pos {
-                break;
-            }
-
-            // Apply viterbi
-            self.viterbi_decode(start_pos, end_pos, tokens);
-            start_pos = end_pos;
-            if start_pos >= tokens.len() - 1 {
-                break;
+    /// Apply hmm model to vector of tokens, decoding each sentence given by its range of token positions
+    pub fn apply(&self, tokens: &mut [Token], sentences: &[Range<usize>]) {
+        for sentence in sentences.iter() {
+            if sentence.is_empty() || sentence.end > tokens.len() {
+                continue;
             }
+            self.viterbi_decode(sentence.start, sentence.end, tokens);
         }
     }
 
@@ -5,7 +5,7 @@
 // Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
 
 use super::{HMM, POSModel, POSModelInterface, POSTag, POSTagModelRepo};
-use crate::tokenizer::{Token, TokenizedInput};
+use crate::tokenizer::{SentenceSplitter, Token, TokenizedInput};
 use crate::vocab::VocabDatabase;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -52,8 +52,9 @@ impl POSTagger {
         // Fix spelling typos
         self.fix_spelling_typos(output, vocab);
 
-        // Resolve via HMM model
-        self.hmm.apply(&mut output.tokens);
+        // Resolve via HMM model, one sentence at a time
+        let sentences = SentenceSplitter::new().token_ranges(&output.original, &output.tokens);
+        self.hmm.apply(&mut output.tokens, &sentences);
 
         // Iterate through words
         for position in 0..output.tokens.len() {
@@ -88,7 +89,8 @@ impl POSTagger {
                 if let Some(correction) =
                     vocab.preprocess.spellchecker.try_correct(position, &output.tokens, vocab)
                 {
-                    output.tokens[position] = correction;
+                    let span = output.tokens[position].span.clone();
+                    output.tokens[position] = Token { span, ..correction };
                 }
             }
         }
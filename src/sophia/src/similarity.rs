@@ -0,0 +1,153 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+//! Normalized semantic similarity measures between tokens, each returning a score between 0.0 and 1.0.
+
+use crate::error::Error;
+use crate::tokenizer::Token;
+use crate::vocab::VocabDatabase;
+use std::collections::{HashMap, HashSet};
+
+/// The measure used to calculate semantic similarity between two words, being path or Wu-Palmer over the hypernym graph, category tree LCA depth, or synonym overlap.
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SimilarityMethod {
+    path,
+    #[default]
+    wu_palmer,
+    category,
+    synonym,
+}
+
+/// Calculates the semantic similarity between two tokens using the given method, returning a score between 0.0 and 1.0.
+pub fn similarity(
+    token1: &Token,
+    token2: &Token,
+    method: SimilarityMethod,
+    vocab: &VocabDatabase,
+) -> f32 {
+    match method {
+        SimilarityMethod::path => path_similarity(token1, token2, vocab),
+        SimilarityMethod::wu_palmer => wu_palmer_similarity(token1, token2, vocab),
+        SimilarityMethod::category => category_similarity(token1, token2, vocab),
+        SimilarityMethod::synonym => synonym_similarity(token1, token2),
+    }
+}
+
+/// Path similarity, being 1 / (1 + shortest path length) between the two tokens through their common hypernyms.
+pub fn path_similarity(token1: &Token, token2: &Token, vocab: &VocabDatabase) -> f32 {
+    if token1.index > 0 && token1.index == token2.index {
+        return 1.0;
+    }
+    let distances1 = hypernym_distances(token1, vocab);
+    let distances2 = hypernym_distances(token2, vocab);
+
+    // Shortest path through any common hypernym
+    let shortest =
+        distances1.iter().filter_map(|(id, d1)| distances2.get(id).map(|d2| d1 + d2)).min();
+
+    match shortest {
+        Some(length) => 1.0 / (1.0 + length as f32),
+        None => 0.0,
+    }
+}
+
+/// Wu-Palmer similarity, being 2 * depth(lcs) / (depth(token1) + depth(token2)) with depths measured through the lowest common hypernym.
+pub fn wu_palmer_similarity(token1: &Token, token2: &Token, vocab: &VocabDatabase) -> f32 {
+    if token1.index > 0 && token1.index == token2.index {
+        return 1.0;
+    }
+    let distances1 = hypernym_distances(token1, vocab);
+    let distances2 = hypernym_distances(token2, vocab);
+
+    // Best score over all common hypernyms
+    let mut score: f32 = 0.0;
+    for (id, d1) in distances1.iter() {
+        let Some(d2) = distances2.get(id) else {
+            continue;
+        };
+        let depth = root_depth(*id, vocab) as f32;
+        score = score.max((2.0 * depth) / (*d1 as f32 + *d2 as f32 + (2.0 * depth)));
+    }
+
+    score
+}
+
+/// Category similarity, being 2 * depth(lca) / (depth(category1) + depth(category2)) over the best matching pair of categories.
+pub fn category_similarity(token1: &Token, token2: &Token, vocab: &VocabDatabase) -> f32 {
+    let categories = &vocab.categories;
+
+    let mut score: f32 = 0.0;
+    for cat1 in token1.categories.iter() {
+        for cat2 in token2.categories.iter() {
+            let Some(lca) = categories.lowest_common_ancestor(*cat1, *cat2) else {
+                continue;
+            };
+            let depth = (categories.get_depth(*cat1) + categories.get_depth(*cat2)) as f32;
+            score = score.max((2.0 * categories.get_depth(lca) as f32) / depth);
+        }
+    }
+
+    score
+}
+
+/// Synonym similarity, being the Jaccard overlap of the two tokens' synonym sets, each including the token itself.
+pub fn synonym_similarity(token1: &Token, token2: &Token) -> f32 {
+    let set1: HashSet<i32> =
+        token1.synonyms.iter().copied().chain([token1.index]).filter(|id| *id > 0).collect();
+    let set2: HashSet<i32> =
+        token2.synonyms.iter().copied().chain([token2.index]).filter(|id| *id > 0).collect();
+
+    let union = set1.union(&set2).count();
+    if union == 0 {
+        return 0.0;
+    }
+    set1.intersection(&set2).count() as f32 / union as f32
+}
+
+/// Returns the shortest distance from the token to itself and each of its hypernyms.
+fn hypernym_distances(token: &Token, vocab: &VocabDatabase) -> HashMap<i32, usize> {
+    let mut distances: HashMap<i32, usize> = vocab.get_hypernyms(token.index).into_iter().collect();
+    if token.index > 0 {
+        distances.insert(token.index, 0);
+    }
+    distances
+}
+
+/// Returns the depth of a token within the hypernym graph, being 1 + the shortest distance to a token with no hypernyms.
+fn root_depth(token_id: i32, vocab: &VocabDatabase) -> usize {
+    let is_root = |id: i32| vocab.get_token(id).is_none_or(|token| token.hypernyms.is_empty());
+    if is_root(token_id) {
+        return 1;
+    }
+
+    // Every path leading into a cycle gives the depth of all hypernyms
+    let hypernyms = vocab.get_hypernyms(token_id);
+    match hypernyms.iter().find(|(id, _)| is_root(*id)) {
+        Some((_, distance)) => distance + 1,
+        None => hypernyms.len() + 1,
+    }
+}
+
+impl TryFrom<&str> for SimilarityMethod {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let res = match value.to_lowercase().as_str() {
+            "path" => Self::path,
+            "wu_palmer" | "wup" => Self::wu_palmer,
+            "category" => Self::category,
+            "synonym" => Self::synonym,
+            _ => {
+                return Err(Error::Generic(format!(
+                    "Invalid similarity method, {}",
+                    value
+                )));
+            }
+        };
+
+        Ok(res)
+    }
+}
@@ -5,9 +5,18 @@
 // Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
 
 use crate::error::Error;
-use crate::interpret::{Interpretation, Interpreter};
-use crate::tokenizer::{Token, TokenizedInput, Tokenizer};
-use crate::vocab::{VocabCategory, VocabDatabase, VocabStats};
+use crate::interpret::{Interpretation, InterpretationStream, Interpreter};
+use crate::pos_tagger::POSTag;
+use crate::similarity::{self, SimilarityMethod};
+use crate::tokenizer::{
+    Sentence, SentenceSplitter, StreamUnit, Token, TokenStream, TokenizedInput, Tokenizer,
+    TruecasedInput,
+};
+use crate::vocab::{
+    UserWord, VocabCategory, VocabDatabase, VocabDatabaseMeta, VocabLoadOptions, VocabSearchFilter,
+    VocabStats,
+};
+use std::io::BufRead;
 
 /// The main entry point for the Sophia natural language processing library, integrating tokenization and interpretation capabilities.
 ///
@@ -27,7 +36,7 @@ impl Sophia {
     ///
     /// # Arguments
     /// - `datadir`: The path to the directory containing the vocabulary database files.
-    /// - `language`: The language code and filename of the .dat vocabulary file (eg. 'en' for 'en.dat' file)
+    /// - `language`: The language code and filename of the .dat vocabulary file (eg. 'en' for 'en.dat' file, or the zstd compressed 'en.dat.zst' file)
     ///
     /// # Returns
     /// A `Result` containing the initialized `Sophia` instance or an `Error` if the vocabulary cannot be loaded.
@@ -44,6 +53,173 @@ impl Sophia {
         })
     }
 
+    /// Creates a new `Sophia` instance, memory-mapping the vocabulary database from the specified directory and language.
+    ///
+    /// Unlike `new()`, the wordlist, tokens and MWE trie are queried in place from the mapped file instead of being fully
+    /// deserialized, giving faster start up and allowing the OS page cache to be shared between multiple processes.
+    ///
+    /// # Arguments
+    /// - `datadir`: The path to the directory containing the vocabulary database files.
+    /// - `language`: The language code and filename of the .mdat vocabulary file (eg. 'en' for 'en.mdat' file)
+    ///
+    /// # Returns
+    /// A `Result` containing the initialized `Sophia` instance or an `Error` if the vocabulary cannot be loaded.
+    ///
+    pub fn new_mapped(datadir: &str, language: &str) -> Result<Self, Error> {
+        let vocab = VocabDatabase::load_mapped(datadir, language)?;
+
+        Ok(Self {
+            datadir: datadir.to_string(),
+            _language: language.to_string(),
+            interpreter: Interpreter::new(&vocab),
+            tokenizer: Tokenizer::new(),
+            vocab,
+        })
+    }
+
+    /// Creates a new `Sophia` instance as per `new_mapped()`, additionally verifying the SHA256 hash of the entire mapped file and its
+    /// signature against the given public key.
+    ///
+    /// Hashing reads every page of the mapped file, so start up is slower than `new_mapped()`.
+    ///
+    /// # Arguments
+    /// - `datadir`: The path to the directory containing the vocabulary database files.
+    /// - `language`: The language code and filename of the .mdat vocabulary file (eg. 'en' for 'en.mdat' file)
+    /// - `public_key`: The 32 byte ed25519 public key the vocabulary database was signed with.
+    ///
+    /// # Returns
+    /// A `Result` containing the initialized `Sophia` instance, or an `Error::Load` if the vocabulary cannot be loaded,
+    /// fails its integrity check, or is not signed by the given key.
+    ///
+    pub fn new_mapped_verified(
+        datadir: &str,
+        language: &str,
+        public_key: &[u8; 32],
+    ) -> Result<Self, Error> {
+        let options = VocabLoadOptions {
+            public_key: Some(*public_key),
+            verify_mapped: true,
+            ..Default::default()
+        };
+        let vocab = VocabDatabase::load_mapped_with(datadir, language, &options)?;
+
+        Ok(Self {
+            datadir: datadir.to_string(),
+            _language: language.to_string(),
+            interpreter: Interpreter::new(&vocab),
+            tokenizer: Tokenizer::new(),
+            vocab,
+        })
+    }
+
+    /// Creates a new `Sophia` instance as per `new()`, additionally verifying the vocabulary database signature against the given public key.
+    ///
+    /// # Arguments
+    /// - `datadir`: The path to the directory containing the vocabulary database files.
+    /// - `language`: The language code and filename of the .dat vocabulary file (eg. 'en' for 'en.dat' file)
+    /// - `public_key`: The 32 byte ed25519 public key the vocabulary database was signed with.
+    ///
+    /// # Returns
+    /// A `Result` containing the initialized `Sophia` instance, or an `Error::Load` if the vocabulary cannot be loaded,
+    /// fails its integrity check, or is not signed by the given key.
+    ///
+    pub fn new_verified(
+        datadir: &str,
+        language: &str,
+        public_key: &[u8; 32],
+    ) -> Result<Self, Error> {
+        let options = VocabLoadOptions {
+            public_key: Some(*public_key),
+            ..Default::default()
+        };
+        let vocab = VocabDatabase::load_with(datadir, language, &options)?;
+
+        Ok(Self {
+            datadir: datadir.to_string(),
+            _language: language.to_string(),
+            interpreter: Interpreter::new(&vocab),
+            tokenizer: Tokenizer::new(),
+            vocab,
+        })
+    }
+
+    /// Gets the metadata of the loaded vocabulary database, including its version, language, creation time and hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let sophia = Sophia::new("./vocab_data", "en")?;
+    ///     let meta = sophia.vocab_meta();
+    ///     let (major, minor, patch) = meta.version();
+    ///     println!("Running vocabulary v{}.{}.{} ({}), hash {}", major, minor, patch, meta.language(), meta.sha256_hash());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn vocab_meta(&self) -> &VocabDatabaseMeta {
+        &self.vocab.meta
+    }
+
+    /// Loads a user dictionary file into the user overlay, which is consulted before the vocabulary database during tokenization.
+    ///
+    /// Files with a .toml extension contain a `[[words]]` table per entry, while all other files are tab delimited text
+    /// with one entry per line as `word  POS  [key=value ...]`, supporting the keys `categories`, `ner` and `type`.  Entries
+    /// containing spaces are added as MWEs.  The vocabulary database file itself is never modified.
+    ///
+    /// # Arguments
+    /// - `filename`: The path to the user dictionary file.
+    ///
+    /// # Returns
+    /// A `Result` containing the number of entries loaded, or an `Error` if the file cannot be read or parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut sophia = Sophia::new("./vocab_data", "en")?;
+    ///     let total = sophia.load_user_dictionary("./vocab_data/user.toml")?;
+    ///     println!("Loaded {} user dictionary entries", total);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn load_user_dictionary(&mut self, filename: &str) -> Result<usize, Error> {
+        self.vocab.load_user_dictionary(filename)
+    }
+
+    /// Adds a word or MWE to the user overlay, which is consulted before the vocabulary database during tokenization.
+    ///
+    /// # Arguments
+    /// - `entry`: The `UserWord` to add, including its POS tag, category paths and NER labels.
+    ///
+    /// # Returns
+    /// A `Result` containing the token ID assigned to the entry, or an `Error` if a category path or NER label does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    /// use sophia::pos_tagger::POSTag;
+    /// use sophia::vocab::UserWord;
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut sophia = Sophia::new("./vocab_data", "en")?;
+    ///     let mut entry = UserWord::new("Acme Widget Pro", POSTag::NNP);
+    ///     entry.ner = vec!["product".to_string()];
+    ///     sophia.add_user_word(&entry)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn add_user_word(&mut self, entry: &UserWord) -> Result<i32, Error> {
+        self.vocab.add_user_word(entry)
+    }
+
     /// Tokenizes the input text into a `TokenizedInput` containing tokens and MWEs.
     ///
     /// This method processes the input string using the `Tokenizer`, breaking it into individual tokens and identifying multi-word entities (MWEs).
@@ -82,6 +258,106 @@ impl Sophia {
         self.tokenizer.encode(input, &self.vocab)
     }
 
+    /// Tokenizes a stream of text such as a long transcript or mail archive, one sentence or paragraph at a time.
+    ///
+    /// Only the current chunk is buffered in memory, and tokenization state such as possessives and negation carries across chunks.
+    ///
+    /// # Arguments
+    /// - `reader`: The buffered reader to stream text from.
+    /// - `unit`: Whether to yield one sentence or one paragraph at a time.
+    ///
+    /// # Returns
+    /// A `TokenStream` iterator yielding a `Result` containing the `TokenizedInput` of each chunk, or an `Error` if reading the stream fails.
+    /// Token spans are relative to the chunk, which begins at the byte offset given by `offset()` within the stream.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    /// use sophia::tokenizer::StreamUnit;
+    /// use std::fs::File;
+    /// use std::io::BufReader;
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let sophia = Sophia::new("./vocab_data", "en")?;
+    ///     let file = File::open("transcript.txt").unwrap();
+    ///
+    ///     for output in sophia.tokenize_stream(BufReader::new(file), StreamUnit::sentence) {
+    ///         let output = output?;
+    ///         for token in output.mwe() {
+    ///             println!("MWE: {}, POS: {}", token.word, token.pos);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn tokenize_stream<R: BufRead>(&self, reader: R, unit: StreamUnit) -> TokenStream<'_, R> {
+        self.tokenizer.encode_stream(reader, unit, &self.vocab)
+    }
+
+    /// Splits input text into sentences, handling abbreviations, initials, decimals, ellipses and quoted boundaries.
+    ///
+    /// Periods following abbreviations (eg. Dr., Jan.) or initials (eg. J. R. R. Tolkien) and within decimals do not end a sentence, and
+    /// question or exclamation marks within quoted speech followed by a lowercase word (eg. "Really?" she asked) are kept within the sentence.
+    ///
+    /// # Arguments
+    /// - `input`: The text to split.
+    ///
+    /// # Returns
+    /// A vector of `Sentence` instances, each containing the trimmed text of the sentence and its byte range within the input.
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let sophia = Sophia::new("./vocab_data", "en")?;
+    ///     let sentences = sophia.sentences("Dr. Smith arrived at 5 p.m. on Jan. 3. He paid $4.50 for coffee.");
+    ///
+    ///     for sentence in sentences.iter() {
+    ///         println!("{:?}: {}", sentence.span, sentence.text);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn sentences(&self, input: &str) -> Vec<Sentence> {
+        SentenceSplitter::new().split(input)
+    }
+
+    /// Restores the canonical capitalization of input text typed in all lowercase or all uppercase, such as chat messages or voice transcripts.
+    ///
+    /// Casing is taken from the vocabulary and MWEs (eg. "new york city" becomes "New York City"), words predicted as proper nouns are
+    /// title cased, and the first word of each sentence is capitalized.  For input already in mixed case, only all lowercase words are changed.
+    ///
+    /// # Arguments
+    /// - `input`: The text to truecase.
+    ///
+    /// # Returns
+    /// A `TruecasedInput` containing the corrected text, plus each changed word along with its position, byte range and the reason for the change.
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let sophia = Sophia::new("./vocab_data", "en")?;
+    ///     let output = sophia.truecase("i flew from new york city to see john last week");
+    ///     println!("Corrected: {}", output.output);
+    ///
+    ///     for change in output.changes.iter() {
+    ///         println!("{} -> {} ({:?})", change.original, change.corrected, change.reason);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn truecase(&self, input: &str) -> TruecasedInput {
+        self.tokenizer.truecase(input, &self.vocab)
+    }
+
     /// Interprets the input text, and returns an `Interpretation` with tokens, MWEs and usable phrases.
     ///
     /// This method first tokenizes the input using the `Tokenizer` and then processes the tokens using the `Interpreter` to generate a structured
@@ -125,6 +401,47 @@ impl Sophia {
         self.interpreter.interpret(input, &self.tokenizer, &self.vocab)
     }
 
+    /// Interprets a stream of text such as a long transcript or mail archive, one sentence or paragraph at a time.
+    ///
+    /// Only the current chunk is buffered in memory, and both tokenization state and antecedents carry across chunks, so pronouns
+    /// may be resolved against nouns from earlier sentences.
+    ///
+    /// # Arguments
+    /// - `reader`: The buffered reader to stream text from.
+    /// - `unit`: Whether to yield one sentence or one paragraph at a time.
+    ///
+    /// # Returns
+    /// An `InterpretationStream` iterator yielding a `Result` containing the `Interpretation` of each chunk, or an `Error` if reading the stream fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    /// use sophia::tokenizer::StreamUnit;
+    /// use std::io;
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let sophia = Sophia::new("./vocab_data", "en")?;
+    ///
+    ///     for output in sophia.interpret_stream(io::stdin().lock(), StreamUnit::paragraph) {
+    ///         let output = output?;
+    ///         for phrase in output.phrases.iter() {
+    ///             println!("Phrase: {:?}", phrase);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn interpret_stream<R: BufRead>(
+        &self,
+        reader: R,
+        unit: StreamUnit,
+    ) -> InterpretationStream<'_, R> {
+        self.interpreter.interpret_stream(reader, unit, &self.tokenizer, &self.vocab)
+    }
+
     /// Gets an individual token by its index id#
     ///
     /// # Arguments
@@ -150,10 +467,7 @@ impl Sophia {
     ///     Ok(())
     /// }
     pub fn get_token(&self, index: i32) -> Option<Token> {
-        let token = self.vocab.words.id2token.get(&index)?;
-        let mut res = token.clone();
-        res.index = index;
-        Some(res)
+        self.vocab.get_token(index)
     }
 
     /// Gets an individual token by word.
@@ -178,19 +492,118 @@ impl Sophia {
     /// }
     pub fn get_word(&self, word: &str) -> Option<Token> {
         // Check wordlist
-        let pos_map = self.vocab.words.wordlist.get(word)?;
+        let pos_map = self.vocab.get_pos_map(word)?;
 
         // Get token
-        let (_, index) = pos_map.first().unwrap();
-        let token = self.vocab.words.id2token.get(index)?;
-        let mut res = token.clone();
-
-        res.index = *index;
+        let (_, index) = pos_map.first()?;
+        let mut res = self.vocab.get_token(*index)?;
         res.potential_pos = pos_map.keys().copied().collect();
 
         Some(res)
     }
 
+    /// Gets every multi-word entity containing a word in any position.
+    ///
+    /// # Arguments
+    /// - `word`: The word to search for (eg. york).
+    ///
+    /// # Returns
+    /// A vector of MWE tokens containing the word, such as new york, new york city and duke of york, with their token IDs,
+    /// POS tags and categories, ordered from most to least frequent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let sophia = Sophia::new("./vocab_data", "en")?;
+    ///     for token in sophia.get_mwes_containing("york") {
+    ///         println!("{} ({}) {}", token.word, token.index, token.pos);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn get_mwes_containing(&self, word: &str) -> Vec<Token> {
+        self.vocab.get_mwes_containing(word)
+    }
+
+    /// Searches the wordlist and MWEs for all entries starting with a prefix, for autocompletion as the user types.
+    ///
+    /// # Arguments
+    /// - `prefix`: The prefix to complete (eg. new yo).
+    /// - `filter`: Restricts results by POS tag and category, and limits the number of results.
+    ///
+    /// # Returns
+    /// A vector of matching tokens, ordered from most to least frequent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    /// use sophia::vocab::VocabSearchFilter;
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let sophia = Sophia::new("./vocab_data", "en")?;
+    ///     let filter = VocabSearchFilter { limit: 10, ..Default::default() };
+    ///     for token in sophia.search_prefix("new yo", &filter) {
+    ///         println!("suggest {}", token.word);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn search_prefix(&self, prefix: &str, filter: &VocabSearchFilter) -> Vec<Token> {
+        self.vocab.search_prefix(prefix, filter)
+    }
+
+    /// Searches the wordlist and MWEs for all entries within an edit distance of a word.
+    ///
+    /// # Arguments
+    /// - `word`: The word to search for, which may be misspelled.
+    /// - `max_distance`: The maximum Levenshtein distance of results.
+    /// - `filter`: Restricts results by POS tag and category, and limits the number of results.
+    ///
+    /// # Returns
+    /// A vector of matching tokens with their edit distance, ordered by distance then frequency.
+    pub fn search_fuzzy(
+        &self,
+        word: &str,
+        max_distance: usize,
+        filter: &VocabSearchFilter,
+    ) -> Vec<(Token, usize)> {
+        self.vocab.search_fuzzy(word, max_distance, filter)
+    }
+
+    /// Searches the wordlist and MWEs for all entries matching a glob pattern, such as `run*` or `b?t`.
+    ///
+    /// # Arguments
+    /// - `pattern`: The glob pattern, where `*` matches any sequence and `?` any single character.
+    /// - `filter`: Restricts results by POS tag and category, and limits the number of results.
+    ///
+    /// # Returns
+    /// A vector of matching tokens, ordered from most to least frequent.
+    pub fn search_glob(&self, pattern: &str, filter: &VocabSearchFilter) -> Vec<Token> {
+        self.vocab.search_glob(pattern, filter)
+    }
+
+    /// Searches the wordlist and MWEs for all entries matching a regular expression.
+    ///
+    /// # Arguments
+    /// - `pattern`: The regular expression, matched against the lowercase word or MWE.
+    /// - `filter`: Restricts results by POS tag and category, and limits the number of results.
+    ///
+    /// # Returns
+    /// A vector of matching tokens ordered from most to least frequent, or an error if the pattern is invalid.
+    pub fn search_regex(
+        &self,
+        pattern: &str,
+        filter: &VocabSearchFilter,
+    ) -> Result<Vec<Token>, Error> {
+        self.vocab.search_regex(pattern, filter)
+    }
+
     /// Gets a category by its path.
     ///
     /// # Arguments
@@ -212,11 +625,350 @@ impl Sophia {
     ///     Ok(())
     /// }
     pub fn get_category(&self, category_path: &str) -> Option<VocabCategory> {
-        self.vocab.categories.get_category_by_path(category_path, &self.vocab)
+        self.vocab.categories.get_category_by_path(category_path, &self.vocab.user)
+    }
+
+    /// Lists the words within a category and all of its descendant categories, with pagination.
+    ///
+    /// # Arguments
+    /// - `category_path`: The full category path to list words of (eg. nouns/transportation)
+    /// - `offset`: The number of words to skip.
+    /// - `limit`: The maximum number of words to return.
+    ///
+    /// # Returns
+    /// A `Option` containing the page of word index id#s sorted by id#, along with the total number of words within the
+    /// category subtree, or None if the category does not exist.
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let sophia = Sophia::new("./vocab_data", "en")?;
+    ///     if let Some((words, total)) = sophia.get_category_words("nouns/transportation", 0, 25) {
+    ///         println!("showing {} of {} words", words.len(), total);
+    ///         for index in words {
+    ///             println!("{}", sophia.get_token(index).unwrap().word);
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    pub fn get_category_words(
+        &self,
+        category_path: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Option<(Vec<i32>, usize)> {
+        self.vocab.categories.get_subtree_words(category_path, offset, limit, &self.vocab.user)
+    }
+
+    /// Gets the full path of a category by its index id# (eg. nouns/transportation/automobile).
+    ///
+    /// # Arguments
+    /// - `category_id`: The index id# of the category, as found within `Token.categories` and `Token.ner`.
+    ///
+    /// # Returns
+    /// A `Option` containing the full category path, or None if the category does not exist.
+    pub fn get_category_path(&self, category_id: i16) -> Option<String> {
+        self.vocab.categories.id2path(category_id)
+    }
+
+    /// Gets the full path of the parent of a category.
+    ///
+    /// # Arguments
+    /// - `category_path`: The full category path (eg. verbs/action/search/retrieve)
+    ///
+    /// # Returns
+    /// A `Option` containing the full path of the parent category, or None if the category is top-level or does not exist.
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let sophia = Sophia::new("./vocab_data", "en")?;
+    ///     if let Some(parent) = sophia.get_category_parent("verbs/action/search/retrieve") {
+    ///         println!("parent is {}, siblings are {:?}", parent, sophia.get_category_siblings("verbs/action/search/retrieve"));
+    ///     }
+    ///     Ok(())
+    /// }
+    pub fn get_category_parent(&self, category_path: &str) -> Option<String> {
+        let categories = &self.vocab.categories;
+        categories.id2path(categories.get_parent(categories.path2id(category_path)?)?)
     }
 
-    /// Returns various statistics regarding the loaded vocabulary file such as total singular / ambiguous words, MWEs, POS tags, and more.
+    /// Gets the full paths of all ancestors of a category, ordered from the top-level category down to the parent.
+    ///
+    /// # Arguments
+    /// - `category_path`: The full category path (eg. verbs/action/search/retrieve)
+    ///
+    /// # Returns
+    /// A vector of full category paths, empty if the category is top-level or does not exist.
+    pub fn get_category_ancestors(&self, category_path: &str) -> Vec<String> {
+        let categories = &self.vocab.categories;
+        match categories.path2id(category_path) {
+            Some(category_id) => categories.get_ancestors(category_id),
+            None => Vec::new(),
+        }
+    }
+
+    /// Gets the full paths of the direct children of a category.
+    ///
+    /// # Arguments
+    /// - `category_path`: The full category path (eg. nouns/transportation)
+    ///
+    /// # Returns
+    /// A vector of full category paths, empty if the category has no children or does not exist.
+    pub fn get_category_children(&self, category_path: &str) -> Vec<String> {
+        let categories = &self.vocab.categories;
+        let category_id = categories.path2id(category_path);
+        self.category_paths(category_id.map(|id| categories.get_children(id)))
+    }
+
+    /// Gets the full paths of all descendants of a category, in depth-first order.
+    ///
+    /// # Arguments
+    /// - `category_path`: The full category path (eg. nouns/transportation)
+    ///
+    /// # Returns
+    /// A vector of full category paths, empty if the category has no descendants or does not exist.
+    pub fn get_category_descendants(&self, category_path: &str) -> Vec<String> {
+        let categories = &self.vocab.categories;
+        let category_id = categories.path2id(category_path);
+        self.category_paths(category_id.map(|id| categories.get_descendants(id)))
+    }
+
+    /// Gets the full paths of all categories sharing the same parent as a category, excluding the category itself.
+    ///
+    /// # Arguments
+    /// - `category_path`: The full category path (eg. nouns/transportation/automobile)
+    ///
+    /// # Returns
+    /// A vector of full category paths, empty if the category has no siblings or does not exist.
+    pub fn get_category_siblings(&self, category_path: &str) -> Vec<String> {
+        let categories = &self.vocab.categories;
+        let category_id = categories.path2id(category_path);
+        self.category_paths(category_id.map(|id| categories.get_siblings(id)))
+    }
+
+    /// Gets the full path of the lowest common ancestor of two categories.
+    ///
+    /// # Arguments
+    /// - `category_path1`: The first full category path (eg. nouns/transportation/automobile)
+    /// - `category_path2`: The second full category path (eg. nouns/transportation/aircraft)
+    ///
+    /// # Returns
+    /// A `Option` containing the full path of the lowest common ancestor, which may be either category itself, or None if
+    /// the categories share no ancestor or either does not exist.
+    pub fn get_category_lca(&self, category_path1: &str, category_path2: &str) -> Option<String> {
+        let categories = &self.vocab.categories;
+        let category_id = categories.lowest_common_ancestor(
+            categories.path2id(category_path1)?,
+            categories.path2id(category_path2)?,
+        )?;
+        categories.id2path(category_id)
+    }
+
+    /// Calculates the semantic similarity between two words, returning a score between 0.0 and 1.0.
+    ///
+    /// Every part of speech of both words is compared, and the highest score is returned.
+    ///
+    /// # Arguments
+    /// - `word1`: The first word to compare.
+    /// - `word2`: The second word to compare.
+    /// - `method`: The `SimilarityMethod` to use, being path or Wu-Palmer over the hypernym graph, category tree LCA depth, or synonym overlap.
+    ///
+    /// # Returns
+    /// The similarity score between 0.0 and 1.0, or 0.0 if either word does not exist.
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    /// use sophia::similarity::SimilarityMethod;
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let sophia = Sophia::new("./vocab_data", "en")?;
+    ///     let score = sophia.word_similarity("car", "truck", SimilarityMethod::wu_palmer);
+    ///     println!("car / truck similarity is {}", score);
+    ///     Ok(())
+    /// }
+    pub fn word_similarity(&self, word1: &str, word2: &str, method: SimilarityMethod) -> f32 {
+        let (Some((_, pos_map1)), Some((_, pos_map2))) =
+            (self.vocab.lookup_word(word1), self.vocab.lookup_word(word2))
+        else {
+            return 0.0;
+        };
+
+        // Compare every part of speech
+        let mut score: f32 = 0.0;
+        for token_id1 in pos_map1.values() {
+            for token_id2 in pos_map2.values() {
+                let token1 = self.vocab.from_int(*token_id1);
+                let token2 = self.vocab.from_int(*token_id2);
+                score = score.max(similarity::similarity(
+                    &token1,
+                    &token2,
+                    method,
+                    &self.vocab,
+                ));
+            }
+        }
+
+        score
+    }
+
+    /// Gets the hypernym chain of a word up to its root, following the first hypernym at each level (eg. car -> vehicle -> object -> entity).
+    ///
+    /// # Arguments
+    /// - `word`: The word to retrieve the hypernym chain of, using its default part of speech.
+    ///
+    /// # Returns
+    /// A vector of `Token` instances ordered from the nearest hypernym to the root, stopping before any cycle.
+    pub fn get_hypernym_chain(&self, word: &str) -> Vec<Token> {
+        let token = self.vocab.from_str(word);
+        let chain = self.vocab.get_hypernym_chain(token.index);
+        chain.into_iter().map(|id| self.vocab.from_int(id)).collect()
+    }
+
+    /// Gets all hyponyms of a word down to the maximum depth, nearest first.
+    ///
+    /// # Arguments
+    /// - `word`: The word to retrieve the hyponyms of, using its default part of speech.
+    /// - `max_depth`: The maximum number of levels to descend, with 1 being direct hyponyms only.
+    ///
+    /// # Returns
+    /// A vector of `Token` instances, each hyponym included once at its shortest distance.
+    pub fn get_hyponyms(&self, word: &str, max_depth: usize) -> Vec<Token> {
+        let token = self.vocab.from_str(word);
+        let hyponyms = self.vocab.get_hyponyms(token.index, max_depth);
+        hyponyms.into_iter().map(|(id, _)| self.vocab.from_int(id)).collect()
+    }
+
+    /// Checks whether a word is a kind of another word, by searching the hypernyms of every part of speech of the word.
+    ///
+    /// # Arguments
+    /// - `word`: The word to check (eg. truck).
+    /// - `ancestor`: The word it may be a kind of (eg. vehicle).
+    ///
+    /// # Returns
+    /// True if any part of speech of the word is the ancestor or has it as a hypernym, false otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let sophia = Sophia::new("./vocab_data", "en")?;
+    ///     if sophia.is_a("truck", "vehicle") {
+    ///         println!("A truck is a vehicle");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn is_a(&self, word: &str, ancestor: &str) -> bool {
+        let (Some((_, pos_map)), Some((_, ancestor_map))) = (
+            self.vocab.lookup_word(word),
+            self.vocab.lookup_word(ancestor),
+        ) else {
+            return false;
+        };
+
+        pos_map.values().any(|token_id| {
+            ancestor_map.values().any(|ancestor_id| self.vocab.is_a(*token_id, *ancestor_id))
+        })
+    }
+
+    /// Gets the hypernyms shared by two words, ordered by their combined distance from both words.
+    ///
+    /// # Arguments
+    /// - `word1`: The first word, using its default part of speech.
+    /// - `word2`: The second word, using its default part of speech.
+    ///
+    /// # Returns
+    /// A vector of `Token` instances, with the lowest common hypernym first.
+    pub fn get_shared_hypernyms(&self, word1: &str, word2: &str) -> Vec<Token> {
+        let (token1, token2) = (self.vocab.from_str(word1), self.vocab.from_str(word2));
+        if token1.index == 0 || token2.index == 0 {
+            return Vec::new();
+        }
+
+        let shared = self.vocab.get_shared_hypernyms(token1.index, token2.index);
+        shared.into_iter().map(|id| self.vocab.from_int(id)).collect()
+    }
+
+    /// Inflects a word into the target part of speech, such as conjugating a verb or pluralizing a noun.
+    ///
+    /// Forms within the vocabulary are used first (eg. go -> went), falling back to English morphological rules for unknown words.
+    ///
+    /// # Arguments
+    /// - `word`: The word to inflect, in any form (eg. go, went, cars).
+    /// - `target`: The target POS tag, being one of VB, VBD, VBG, VBN, VBP, VBZ, NN, NNS, JJ, JJR or JJS.
+    ///
+    /// # Returns
+    /// The inflected word, or None if the target POS tag is not supported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    /// use sophia::pos_tagger::POSTag;
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let sophia = Sophia::new("./vocab_data", "en")?;
+    ///     assert_eq!(sophia.inflect("go", POSTag::VBD), Some("went".to_string()));
+    ///     assert_eq!(sophia.inflect("stop", POSTag::VBG), Some("stopping".to_string()));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn inflect(&self, word: &str, target: POSTag) -> Option<String> {
+        self.vocab.inflect(word, target)
+    }
+
+    /// Lemmatizes tokenized input, returning the lemma of each token with MWEs combined, based on their assigned POS tags.
+    ///
+    /// Multi-word verb phrases resolve to the lemma of their verb (eg. will have gone -> go), while other MWEs remain whole.
+    ///
+    /// # Arguments
+    /// - `input`: The tokenized input, as returned by `tokenize()`.
+    ///
+    /// # Returns
+    /// A vector of lemmas, one for each token when iterating over the MWEs of the input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #![no_run]
+    /// use sophia::{Sophia, Error};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let sophia = Sophia::new("./vocab_data", "en")?;
+    ///     let output = sophia.tokenize("He will have gone to the stores.");
+    ///     let lemmas = sophia.lemmatize(&output);
+    ///     println!("{}", lemmas.join(" "));    // he go to the store .
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn lemmatize(&self, input: &TokenizedInput) -> Vec<String> {
+        input.mwe().map(|token| token.lemma(&self.vocab)).collect()
+    }
+
+    /// Returns various statistics regarding the loaded vocabulary file such as total singular / ambiguous words, MWEs, POS tags, category and model breakdowns, memory use, and more.
     pub fn get_vocab_stats(&self) -> VocabStats {
         VocabStats::compile(&self.vocab)
     }
+
+    /// Converts category index id#s into their full category paths.
+    fn category_paths(&self, category_ids: Option<Vec<i16>>) -> Vec<String> {
+        category_ids
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|id| self.vocab.categories.id2path(id))
+            .collect()
+    }
 }
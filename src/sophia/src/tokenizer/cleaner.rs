@@ -17,6 +17,7 @@ static NUMERIC_CHARS: &[char] = &['.', ',', '^', '*', '/', ':'];
 #[derive(Default)]
 pub struct TokenCleaner {
     chars: Vec<char>,
+    offset: usize,
     word_len: usize,
     numeric_len: usize,
     pub is_numeric: bool,
@@ -60,21 +61,24 @@ impl TokenCleaner {
     /// Scans characters in a word, stripping prefixes/suffixes, updating possessive status, and tracking numeric/special properties.
     fn scan_chars(&mut self, word: &mut String, vocab: &VocabDatabase, buffer: &mut Buffer) {
         // Check for possession
-        if word.ends_with("'s") {
+        if let Some(stem) = word.strip_suffix("'s").or_else(|| word.strip_suffix("\u{2019}s")) {
             buffer.is_possessive = true;
-            *word = word[..word.len() - 2].to_string();
+            *word = stem.to_string();
         }
 
         // Iterate through chars
         let mut in_prefix = true;
-        for (x, c) in word.chars().enumerate() {
+        for (x, c) in word.char_indices() {
             // Check for currency
             if x == 0 && (c == '-' || c == '+' || c.is_currency_symbol()) {
                 self.chars.push(c);
 
             // Prefix symbol
             } else if in_prefix && SPECIAL_CHARS.contains(&c) {
-                buffer.push_token(Token::prefix(&c.to_string(), vocab));
+                let mut token = Token::prefix(&c.to_string(), vocab);
+                token.span = buffer.normalized.input_span(&buffer.sub_span(x, &c.to_string()));
+                buffer.push_token(token);
+                self.offset = x + c.len_utf8();
             } else {
                 in_prefix = false;
 
@@ -95,6 +99,7 @@ impl TokenCleaner {
 
     /// Classifies a numeric word as a time or general numeric token, pushing it to the buffer.
     fn classify_numeric(&mut self, word: &str, vocab: &VocabDatabase, buffer: &mut Buffer) {
+        buffer.span = buffer.sub_span(self.offset, &self.chars.iter().collect::<String>());
         if self.is_time() {
             buffer.push_token(Token::special(word, "|time|", "", "", vocab));
         } else {
@@ -115,6 +120,7 @@ impl TokenCleaner {
 
             if self.is_decade(&suffix) {
                 let value = self.chars[..self.numeric_len].iter().collect::<String>();
+                buffer.span = buffer.sub_span(self.offset, &self.chars.iter().collect::<String>());
                 buffer.push_token(Token::special(
                     word,
                     "|date_period|",
@@ -125,18 +131,25 @@ impl TokenCleaner {
                 return None;
             } else if let Some((suffix_tag, _)) = vocab.preprocess.hashes.get(&suffix) {
                 let value = self.chars[..self.numeric_len].iter().collect::<String>();
+                buffer.span = buffer.sub_span(self.offset, &self.chars.iter().collect::<String>());
                 buffer.push_token(Token::special(word, suffix_tag, &value, &suffix, vocab));
                 return None;
             }
         }
 
         // Add suffix to buffer
+        let cleaned: String = self.chars[..self.word_len].iter().collect();
+        let mut offset = self.offset + cleaned.len();
         for c in self.chars[self.word_len..].iter() {
-            buffer.prepend_suffix(&Token::suffix(&c.to_string(), vocab));
+            let mut token = Token::suffix(&c.to_string(), vocab);
+            token.span = buffer.normalized.input_span(&buffer.sub_span(offset, &c.to_string()));
+            buffer.prepend_suffix(&token);
+            offset += c.len_utf8();
         }
 
         if self.word_len > 0 {
-            Some(self.chars[..self.word_len].iter().collect())
+            buffer.span = buffer.sub_span(self.offset, &cleaned);
+            Some(cleaned)
         } else {
             None
         }
@@ -0,0 +1,316 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+use super::{Buffer, Token, Tokenizer};
+use crate::error::Error;
+use crate::pos_tagger::POSTag;
+use crate::vocab::VocabDatabase;
+use regex::Regex;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+static OPENING_CHARS: &[char] = &['(', '[', '{', '<', '"', '\''];
+static CLOSING_CHARS: &[char] = &['.', ',', ';', ':', '!', '?', ')', ']', '}', '>', '"', '\''];
+
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?i:mailto:)?[A-Za-z0-9._%+\-]+@[A-Za-z0-9\-]+(\.[A-Za-z0-9\-]+)*\.[A-Za-z]{2,}$")
+        .unwrap()
+});
+static URL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?i:(https?|ftp)://[^\s/?#]+|www\.[A-Za-z0-9\-]+(\.[A-Za-z0-9\-]+)+)([/?#]\S*)?$")
+        .unwrap()
+});
+static PHONE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^((\+\d{1,3}[\-.]?)?(\(\d{3}\) ?|\d{3}[\-.]?)\d{3}[\-.]\d{4}|\+\d{7,15}|\+\d{1,3}([\-.]\d{2,4}){2,5})$",
+    )
+    .unwrap()
+});
+static HASHTAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^#[\p{L}\p{N}_]*\p{L}[\p{L}\p{N}_]*$").unwrap());
+static MENTION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^@[A-Za-z0-9_]+(\.[A-Za-z0-9_]+)*$").unwrap());
+
+/// Structured entities recognized within input before cleaning, each tokenized as a single system tag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntityType {
+    email,
+    url,
+    phone,
+    hashtag,
+    mention,
+}
+
+/// An entity recognized within a word, with the byte range of the entity within the word and its normalized form.
+#[derive(Clone, Debug)]
+pub struct Entity {
+    pub entity_type: EntityType,
+    pub range: Range<usize>,
+    pub value: String,
+}
+
+impl EntityType {
+    /// Returns all entity types.
+    pub fn all() -> [Self; 5] {
+        [
+            Self::email,
+            Self::url,
+            Self::phone,
+            Self::hashtag,
+            Self::mention,
+        ]
+    }
+
+    /// Returns the system tag of the entity type (eg. |email|).
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Self::email => "|email|",
+            Self::url => "|url|",
+            Self::phone => "|phone|",
+            Self::hashtag => "|hashtag|",
+            Self::mention => "|mention|",
+        }
+    }
+}
+
+impl Entity {
+    /// Detects an email address, URL, phone number, hashtag or mention within a word, excluding any surrounding punctuation.
+    pub fn detect(word: &str) -> Option<Self> {
+        let mut start = word.len() - word.trim_start_matches(OPENING_CHARS).len();
+        let mut end = word.trim_end_matches(CLOSING_CHARS).len();
+        if start >= end {
+            return None;
+        }
+
+        // Keep the opening bracket of an area code (eg. (555) 123-4567)
+        if word[..start].ends_with('(') && PHONE.is_match(&word[start - 1..end]) {
+            start -= 1;
+        }
+
+        // Keep closing brackets balanced within URLs (eg. wiki/Rust_(language))
+        let core = &word[start..end];
+        if URL.is_match(core) {
+            while word[end..].starts_with(')')
+                && word[start..end].matches('(').count() > word[start..end].matches(')').count()
+            {
+                end += 1;
+            }
+        }
+
+        let core = &word[start..end];
+        let (entity_type, value) = if EMAIL.is_match(core) {
+            let address = core.get(..7).filter(|prefix| prefix.eq_ignore_ascii_case("mailto:"));
+            let address = match address {
+                Some(prefix) => &core[prefix.len()..],
+                None => core,
+            };
+            (EntityType::email, address.to_lowercase())
+        } else if URL.is_match(core) {
+            (EntityType::url, normalize_url(core))
+        } else if PHONE.is_match(core) {
+            let digits: String = core.chars().filter(|c| c.is_ascii_digit()).collect();
+            if !(7..=15).contains(&digits.len()) {
+                return None;
+            }
+            match core.starts_with('+') {
+                true => (EntityType::phone, format!("+{}", digits)),
+                false => (EntityType::phone, digits),
+            }
+        } else if HASHTAG.is_match(core) {
+            (EntityType::hashtag, core[1..].to_lowercase())
+        } else if MENTION.is_match(core) {
+            (EntityType::mention, core[1..].to_lowercase())
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            entity_type,
+            range: start..end,
+            value,
+        })
+    }
+}
+
+impl Tokenizer {
+    /// Checks the word for an email address, URL, phone number, hashtag or mention, adding it along with any surrounding
+    /// punctuation to the buffer, and returns true if found.
+    pub(super) fn push_entity(
+        &self,
+        word: &str,
+        vocab: &VocabDatabase,
+        buffer: &mut Buffer,
+    ) -> bool {
+        // Join a parenthesized area code with the remainder of the phone number (eg. (555) 123-4567)
+        let joined = match (word.ends_with(')'), buffer.words.first()) {
+            (true, Some(next)) if buffer.words.len() > 1 => format!("{} {}", word, next),
+            _ => String::new(),
+        };
+        let word = match Entity::detect(&joined) {
+            Some(entity)
+                if entity.entity_type == EntityType::phone && entity.range.start < word.len() =>
+            {
+                buffer.words.remove(0);
+                let next_span = buffer.spans.remove(0);
+                buffer.span = buffer.span.start..next_span.end;
+                joined.as_str()
+            }
+            _ => word,
+        };
+
+        // Check for possession (eg. @alice's)
+        let (word, is_possessive) = match word.strip_suffix("'s") {
+            Some(stem) => (stem, true),
+            None => (word, false),
+        };
+        let Some(entity) = Entity::detect(word) else {
+            return false;
+        };
+        let word_span = buffer.span.clone();
+
+        // Leading punctuation
+        for (x, c) in word[..entity.range.start].char_indices() {
+            let mut token = Token::prefix(&c.to_string(), vocab);
+            token.span = buffer.normalized.input_span(&buffer.sub_span(x, &c.to_string()));
+            buffer.push_token(token);
+        }
+
+        // Trailing punctuation
+        for (x, c) in word[entity.range.end..].char_indices() {
+            let offset = entity.range.end + x;
+            let mut token = Token::suffix(&c.to_string(), vocab);
+            token.span = buffer.normalized.input_span(&buffer.sub_span(offset, &c.to_string()));
+            buffer.suffix.push(token);
+        }
+
+        let text = &word[entity.range.clone()];
+        buffer.span = buffer.sub_span(entity.range.start, text);
+        let mut token = Token::special(text, entity.entity_type.tag(), &entity.value, "", vocab);
+        token.pos = POSTag::SYS;
+        buffer.is_possessive = is_possessive;
+        buffer.push_token(token);
+        buffer.push_suffix();
+
+        buffer.span = word_span;
+        true
+    }
+}
+
+/// Normalizes a URL, lowercasing its scheme and host and adding a scheme where omitted (eg. www.example.com).
+fn normalize_url(url: &str) -> String {
+    let (scheme, rest) = match url.find("://") {
+        Some(pos) => (url[..pos].to_lowercase(), &url[pos + 3..]),
+        None => ("http".to_string(), url),
+    };
+
+    let host_len = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (host, path) = rest.split_at(host_len);
+    let path = if path == "/" { "" } else { path };
+
+    format!("{}://{}{}", scheme, host.to_lowercase(), path)
+}
+
+impl TryFrom<&str> for EntityType {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let res = match value.trim_matches('|').to_lowercase().as_str() {
+            "email" => Self::email,
+            "url" => Self::url,
+            "phone" => Self::phone,
+            "hashtag" => Self::hashtag,
+            "mention" => Self::mention,
+            _ => return Err(Error::Generic(format!("Invalid entity type, {}", value))),
+        };
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(word: &str) -> Option<(EntityType, String)> {
+        Entity::detect(word).map(|entity| (entity.entity_type, entity.value))
+    }
+
+    #[test]
+    fn test_emails() {
+        assert_eq!(
+            detect("Bob.Smith@Example.com"),
+            Some((EntityType::email, "bob.smith@example.com".to_string()))
+        );
+        assert_eq!(
+            detect("mailto:bob@example.co.uk"),
+            Some((EntityType::email, "bob@example.co.uk".to_string()))
+        );
+        assert_eq!(detect("bob@example"), None);
+    }
+
+    #[test]
+    fn test_urls() {
+        assert_eq!(
+            detect("HTTPS://Example.com/"),
+            Some((EntityType::url, "https://example.com".to_string()))
+        );
+        assert_eq!(
+            detect("www.example.com/Path?q=1"),
+            Some((
+                EntityType::url,
+                "http://www.example.com/Path?q=1".to_string()
+            ))
+        );
+        assert_eq!(detect("example.com"), None);
+    }
+
+    #[test]
+    fn test_phones() {
+        assert_eq!(
+            detect("555-123-4567"),
+            Some((EntityType::phone, "5551234567".to_string()))
+        );
+        assert_eq!(
+            detect("(555) 123-4567"),
+            Some((EntityType::phone, "5551234567".to_string()))
+        );
+        assert_eq!(
+            detect("+44-20-7946-0958"),
+            Some((EntityType::phone, "+442079460958".to_string()))
+        );
+        assert_eq!(detect("+1.5.3"), None);
+        assert_eq!(detect("+1.22.33"), None);
+        assert_eq!(detect("2024-01-15"), None);
+    }
+
+    #[test]
+    fn test_hashtags_and_mentions() {
+        assert_eq!(
+            detect("#RustLang"),
+            Some((EntityType::hashtag, "rustlang".to_string()))
+        );
+        assert_eq!(
+            detect("@Alice"),
+            Some((EntityType::mention, "alice".to_string()))
+        );
+        assert_eq!(detect("#123"), None);
+    }
+
+    #[test]
+    fn test_punctuation() {
+        let word = "(www.example.com/wiki/Rust_(language)).";
+        let entity = Entity::detect(word).unwrap();
+        assert_eq!(&word[entity.range], "www.example.com/wiki/Rust_(language)");
+
+        let word = "(bob@example.com),";
+        let entity = Entity::detect(word).unwrap();
+        assert_eq!(&word[entity.range], "bob@example.com");
+
+        let word = "\"(555)123-4567\".";
+        let entity = Entity::detect(word).unwrap();
+        assert_eq!(&word[entity.range], "(555)123-4567");
+    }
+}
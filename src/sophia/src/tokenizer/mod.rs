@@ -4,12 +4,22 @@
 // License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
 // Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
 
+pub use self::entities::{Entity, EntityType};
 pub use self::input::{MWE, TokenizedInput};
+pub use self::sentence::{Sentence, SentenceSplitter};
+pub use self::stream::{ChunkReader, DEFAULT_MAX_CHUNK_SIZE, StreamUnit, TokenStream};
 pub use self::token::{Token, TokenType};
-pub use self::tokenizer::{Buffer, Tokenizer};
+pub use self::tokenizer::{Buffer, Tokenizer, TokenizerState};
+pub use self::truecase::{TruecaseChange, TruecaseReason, TruecasedInput};
+pub use self::unicode::UnicodeNormalization;
 pub use cleaner::TokenCleaner;
 
 mod cleaner;
+mod entities;
 mod input;
+mod sentence;
+mod stream;
 pub mod token;
 mod tokenizer;
+mod truecase;
+mod unicode;
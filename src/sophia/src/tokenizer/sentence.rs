@@ -0,0 +1,316 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+use super::Token;
+use std::ops::Range;
+
+/// Abbreviations that never end a sentence, as they are always followed by a name, number or example.
+static ABBREVIATIONS: &[&str] = &[
+    "mr", "mrs", "ms", "dr", "prof", "st", "mt", "gen", "rev", "sen", "rep", "gov", "capt", "lt",
+    "col", "sgt", "cpl", "pvt", "feb", "apr", "jun", "jul", "aug", "sep", "sept", "oct", "nov",
+    "tue", "tues", "thu", "thur", "thurs", "fri", "vs", "vol", "vols", "e.g", "i.e", "cf", "viz",
+];
+
+/// Abbreviations that are also ordinary words or names (eg. no, mar), only treated as an abbreviation when followed by a number.
+static NUMERIC_ABBREVIATIONS: &[&str] = &["no", "nos", "fig", "figs", "jan", "mar", "dec", "mon"];
+
+/// Abbreviations that may end a sentence, only doing so when the next word is capitalized.
+static TERMINAL_ABBREVIATIONS: &[&str] = &[
+    "etc", "inc", "ltd", "co", "corp", "jr", "sr", "al", "a.m", "p.m", "approx", "est", "ft",
+    "lbs", "oz",
+];
+
+static TERMINATORS: &[char] = &['.', '!', '?', '\u{2026}'];
+static OPENING_CHARS: &[char] = &['"', '\'', '(', '[', '\u{201C}', '\u{2018}'];
+static CLOSING_CHARS: &[char] = &['"', '\'', ')', ']', '\u{201D}', '\u{2019}'];
+
+/// A single sentence within input text, along with its byte range within the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sentence {
+    pub text: String,
+    pub span: Range<usize>,
+}
+
+/// Splits input text into sentences, handling abbreviations, initials, decimals, ellipses and quoted boundaries.
+///
+/// Sentences end at a blank line, or at terminal punctuation followed by whitespace, including any closing quotes or brackets.
+/// Single line breaks within hard-wrapped text are joined into the current sentence, unless they follow terminal punctuation.
+/// Periods following abbreviations (eg. Dr., Feb.) or initials (eg. J. R. R. Tolkien) do not end a sentence, nor do
+/// ellipses or abbreviations such as p.m. unless the next word is capitalized.
+#[derive(Default)]
+pub struct SentenceSplitter {}
+
+impl SentenceSplitter {
+    /// Creates a new SentenceSplitter instance.
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Splits the input into sentences, with surrounding whitespace trimmed and empty sentences excluded.
+    pub fn split(&self, input: &str) -> Vec<Sentence> {
+        self.split_spans(input)
+            .into_iter()
+            .map(|span| Sentence {
+                text: input[span.clone()].to_string(),
+                span,
+            })
+            .collect()
+    }
+
+    /// Groups tokens into sentences using their spans, returning the range of token positions within each sentence.
+    pub fn token_ranges(&self, input: &str, tokens: &[Token]) -> Vec<Range<usize>> {
+        let sentences = self.split_spans(input);
+
+        let (mut res, mut start, mut current): (Vec<Range<usize>>, usize, usize) =
+            (Vec::new(), 0, 0);
+        for (x, token) in tokens.iter().enumerate() {
+            let index = sentences.iter().rposition(|s| s.start <= token.span.start).unwrap_or(0);
+            if index > current && x > start {
+                res.push(start..x);
+                start = x;
+            }
+            current = current.max(index);
+        }
+
+        if start < tokens.len() {
+            res.push(start..tokens.len());
+        }
+        res
+    }
+
+    /// Splits the input into the byte ranges of each sentence.
+    fn split_spans(&self, input: &str) -> Vec<Range<usize>> {
+        let chars: Vec<(usize, char)> = input.char_indices().collect();
+        let mut spans: Vec<Range<usize>> = Vec::new();
+        let mut start = 0;
+
+        let mut x = 0;
+        while x < chars.len() {
+            let (pos, c) = chars[x];
+            if c == '\n' {
+                // Blank line
+                let next = chars[x + 1..].iter().find(|(_, c)| !c.is_whitespace() || *c == '\n');
+                if let Some((_, '\n')) = next {
+                    spans.push(start..pos);
+                    start = pos + 1;
+                }
+                x += 1;
+                continue;
+            } else if !TERMINATORS.contains(&c) {
+                x += 1;
+                continue;
+            }
+
+            // Run of terminators, followed by any closing quotes or brackets
+            let mut end = x;
+            while end < chars.len() && TERMINATORS.contains(&chars[end].1) {
+                end += 1;
+            }
+            let terminators: String = chars[x..end].iter().map(|(_, c)| c).collect();
+            let quoted = end < chars.len() && CLOSING_CHARS.contains(&chars[end].1);
+            while end < chars.len() && CLOSING_CHARS.contains(&chars[end].1) {
+                end += 1;
+            }
+
+            // Must be followed by whitespace, which excludes decimals and dotted abbreviations (eg. 5.50, e.g.)
+            if end < chars.len() && !chars[end].1.is_whitespace() {
+                x = end;
+                continue;
+            }
+
+            // Line break after terminal punctuation always ends the sentence
+            let is_line_end =
+                chars[end..].iter().take_while(|(_, c)| c.is_whitespace()).any(|(_, c)| *c == '\n');
+
+            let word = self.previous_word(input, start, pos);
+            let next = chars[end..]
+                .iter()
+                .map(|(_, c)| *c)
+                .find(|c| !c.is_whitespace() && !OPENING_CHARS.contains(c));
+            if is_line_end || self.is_boundary(&terminators, word, next, quoted) {
+                let byte_end = chars.get(end).map(|(pos, _)| *pos).unwrap_or(input.len());
+                spans.push(start..byte_end);
+                start = byte_end;
+            }
+            x = end;
+        }
+        spans.push(start..input.len());
+
+        // Trim whitespace
+        spans
+            .into_iter()
+            .filter_map(|span| {
+                let text = &input[span.clone()];
+                let trimmed = text.trim_start();
+                let start = span.start + (text.len() - trimmed.len());
+                let end = start + trimmed.trim_end().len();
+                if start < end { Some(start..end) } else { None }
+            })
+            .collect()
+    }
+
+    /// Determines whether terminal punctuation ends a sentence, given the word before it and the first character of the next word.
+    fn is_boundary(&self, terminators: &str, word: &str, next: Option<char>, quoted: bool) -> bool {
+        let Some(next) = next else {
+            return true;
+        };
+
+        // Question or exclamation, unless within quoted speech (eg. "Really?" she asked)
+        if terminators.contains(['!', '?']) {
+            return !(quoted && next.is_lowercase());
+        }
+
+        // Ellipsis
+        if terminators.len() > 1 || terminators.contains('\u{2026}') {
+            return next.is_uppercase();
+        }
+
+        let lower = word.to_lowercase();
+        if ABBREVIATIONS.contains(&lower.as_str())
+            || (NUMERIC_ABBREVIATIONS.contains(&lower.as_str()) && next.is_ascii_digit())
+        {
+            return false;
+        }
+
+        // Initials (eg. John F. Kennedy), excluding the pronoun I
+        let mut letters = word.chars();
+        if let (Some(first), None) = (letters.next(), letters.next())
+            && first.is_uppercase()
+            && first != 'I'
+        {
+            return false;
+        }
+
+        if TERMINAL_ABBREVIATIONS.contains(&lower.as_str()) || word.contains('.') {
+            return next.is_uppercase();
+        }
+        true
+    }
+
+    /// Returns the word immediately before the byte position, excluding any opening quotes or brackets.
+    fn previous_word<'a>(&self, input: &'a str, start: usize, pos: usize) -> &'a str {
+        let text = &input[start..pos];
+        let word = match text.rfind(char::is_whitespace) {
+            Some(index) => &text[index..],
+            None => text,
+        };
+        word.trim_start_matches(|c: char| c.is_whitespace() || OPENING_CHARS.contains(&c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(input: &str) -> Vec<String> {
+        SentenceSplitter::new().split(input).into_iter().map(|sentence| sentence.text).collect()
+    }
+
+    #[test]
+    fn test_abbreviations() {
+        assert_eq!(
+            split("Dr. Smith arrived at 5 p.m. on Jan. 3. He left."),
+            vec!["Dr. Smith arrived at 5 p.m. on Jan. 3.", "He left."]
+        );
+        assert_eq!(
+            split("See No. 5 and Fig. 2 for details."),
+            vec!["See No. 5 and Fig. 2 for details."]
+        );
+        assert_eq!(
+            split("The answer is no. We left."),
+            vec!["The answer is no.", "We left."]
+        );
+        assert_eq!(
+            split("It was the 3rd of Mar. Then it rained."),
+            vec!["It was the 3rd of Mar.", "Then it rained."]
+        );
+        assert_eq!(
+            split("We bought apples, pears, etc. and left."),
+            vec!["We bought apples, pears, etc. and left."]
+        );
+        assert_eq!(
+            split("We bought apples, pears, etc. Then we left."),
+            vec!["We bought apples, pears, etc.", "Then we left."]
+        );
+    }
+
+    #[test]
+    fn test_initials() {
+        assert_eq!(
+            split("J. R. R. Tolkien wrote it. John F. Kennedy read it."),
+            vec!["J. R. R. Tolkien wrote it.", "John F. Kennedy read it."]
+        );
+        assert_eq!(
+            split("So am I. Then we left."),
+            vec!["So am I.", "Then we left."]
+        );
+    }
+
+    #[test]
+    fn test_decimals() {
+        assert_eq!(
+            split("It costs $4.50 today. Pi is 3.14 roughly."),
+            vec!["It costs $4.50 today.", "Pi is 3.14 roughly."]
+        );
+    }
+
+    #[test]
+    fn test_ellipses() {
+        assert_eq!(split("Well... maybe not."), vec!["Well... maybe not."]);
+        assert_eq!(
+            split("I waited... Nobody came."),
+            vec!["I waited...", "Nobody came."]
+        );
+        assert_eq!(
+            split("I waited\u{2026} Nobody came."),
+            vec!["I waited\u{2026}", "Nobody came."]
+        );
+    }
+
+    #[test]
+    fn test_quotes() {
+        assert_eq!(
+            split("\"Really?\" she asked. \"Yes!\" He nodded."),
+            vec!["\"Really?\" she asked.", "\"Yes!\"", "He nodded."]
+        );
+        assert_eq!(
+            split("He said (quietly.) Then left."),
+            vec!["He said (quietly.)", "Then left."]
+        );
+    }
+
+    #[test]
+    fn test_line_breaks() {
+        assert_eq!(
+            split("This sentence is hard\nwrapped across two lines. Next one."),
+            vec![
+                "This sentence is hard\nwrapped across two lines.",
+                "Next one."
+            ]
+        );
+        assert_eq!(
+            split("First line.\nSecond line"),
+            vec!["First line.", "Second line"]
+        );
+        assert_eq!(
+            split("A heading\n\nThe paragraph"),
+            vec!["A heading", "The paragraph"]
+        );
+        assert_eq!(
+            split("A heading\n  \n  The paragraph"),
+            vec!["A heading", "The paragraph"]
+        );
+    }
+
+    #[test]
+    fn test_spans() {
+        let input = "  One. Two.  ";
+        let sentences = SentenceSplitter::new().split(input);
+        assert_eq!(sentences.len(), 2);
+        assert_eq!(&input[sentences[0].span.clone()], "One.");
+        assert_eq!(&input[sentences[1].span.clone()], "Two.");
+    }
+}
@@ -0,0 +1,307 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+use super::{SentenceSplitter, TokenizedInput, Tokenizer, TokenizerState};
+use crate::error::Error;
+use crate::vocab::VocabDatabase;
+use std::io::{BufRead, Read};
+
+/// The default maximum size in bytes of a single chunk, after which buffered input is yielded even if incomplete.
+pub const DEFAULT_MAX_CHUNK_SIZE: usize = 65536;
+
+/// The unit of text yielded at a time when streaming input.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamUnit {
+    #[default]
+    sentence,
+    paragraph,
+}
+
+/// Reads text from a buffered reader, splitting it into sentences or paragraphs while only buffering the current chunk.
+///
+/// Chunks longer than `max_chunk_size` bytes are yielded as they stand, keeping memory bounded regardless of the input.
+pub struct ChunkReader<R: BufRead> {
+    reader: R,
+    pub unit: StreamUnit,
+    pub max_chunk_size: usize,
+    pending: String,
+    partial: Vec<u8>,
+    pending_offset: usize,
+    offset: usize,
+    is_eof: bool,
+}
+
+/// Streams tokenized input from a buffered reader, one sentence or paragraph at a time.
+///
+/// Tokenization state such as the previous tag, possessives and negation is carried across chunks, so each chunk is tokenized as
+/// it would be within the full text.
+pub struct TokenStream<'a, R: BufRead> {
+    chunks: ChunkReader<R>,
+    tokenizer: &'a Tokenizer,
+    vocab: &'a VocabDatabase,
+    state: TokenizerState,
+}
+
+impl<R: BufRead> ChunkReader<R> {
+    /// Creates a new ChunkReader over the reader, yielding the given unit of text.
+    pub fn new(reader: R, unit: StreamUnit) -> Self {
+        Self {
+            reader,
+            unit,
+            max_chunk_size: DEFAULT_MAX_CHUNK_SIZE,
+            pending: String::new(),
+            partial: Vec::new(),
+            pending_offset: 0,
+            offset: 0,
+            is_eof: false,
+        }
+    }
+
+    /// Returns the byte offset within the stream of the most recently yielded chunk.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Reads the next line into the pending text, up to the maximum chunk size, returning false once the end of input is reached.
+    fn read_line(&mut self) -> Result<bool, Error> {
+        let limit = self.max_chunk_size.saturating_sub(self.pending.len()).max(1) as u64;
+        let bytes = match self.reader.by_ref().take(limit).read_until(b'\n', &mut self.partial) {
+            Ok(r) => r,
+            Err(e) => {
+                return Err(Error::Generic(format!(
+                    "Unable to read input stream, {}",
+                    e
+                )));
+            }
+        };
+
+        // Keep any incomplete UTF-8 sequence cut off by the limit for the next read
+        let valid = match std::str::from_utf8(&self.partial) {
+            Ok(text) => text.len(),
+            Err(e) if e.error_len().is_none() && bytes > 0 => e.valid_up_to(),
+            Err(e) => {
+                return Err(Error::Generic(format!(
+                    "Input stream contains invalid UTF-8, {}",
+                    e
+                )));
+            }
+        };
+
+        let text = String::from_utf8(self.partial.drain(..valid).collect()).unwrap_or_default();
+        self.pending.push_str(&text);
+        Ok(bytes > 0)
+    }
+
+    /// Removes and returns the next complete chunk from the pending text, or all remaining text if `flush` is true.
+    fn take_chunk(&mut self, flush: bool) -> Option<String> {
+        let end = match self.unit {
+            StreamUnit::sentence => {
+                // A sentence is only complete once the next one has started
+                let sentences = SentenceSplitter::new().split(&self.pending);
+                match (sentences.get(1), sentences.first()) {
+                    (Some(_), Some(first)) => Some((first.span.end, first.span.end)),
+                    _ => None,
+                }
+            }
+            StreamUnit::paragraph => self.paragraph_end(),
+        };
+
+        // Split oversized chunks at the last whitespace, so words are not broken apart
+        let (end, consumed) = match end {
+            Some(r) => r,
+            None if flush => {
+                let end = match self.is_eof {
+                    true => self.pending.len(),
+                    false => self
+                        .pending
+                        .rfind(char::is_whitespace)
+                        .filter(|pos| *pos > 0)
+                        .unwrap_or(self.pending.len()),
+                };
+                (end, end)
+            }
+            None => return None,
+        };
+
+        let chunk = &self.pending[..end];
+        let trimmed = chunk.trim_start();
+        self.offset = self.pending_offset + (chunk.len() - trimmed.len());
+        let chunk = trimmed.trim_end().to_string();
+
+        self.pending.drain(..consumed);
+        self.pending_offset += consumed;
+        if chunk.is_empty() { None } else { Some(chunk) }
+    }
+
+    /// Returns the end of the first paragraph within the pending text and the end of the blank line following it, if complete.
+    fn paragraph_end(&self) -> Option<(usize, usize)> {
+        let mut pos = 0;
+        for line in self.pending.split_inclusive('\n') {
+            if line.ends_with('\n')
+                && line.trim().is_empty()
+                && !self.pending[..pos].trim().is_empty()
+            {
+                return Some((pos, pos + line.len()));
+            }
+            pos += line.len();
+        }
+
+        None
+    }
+}
+
+impl<R: BufRead> Iterator for ChunkReader<R> {
+    type Item = Result<String, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(chunk) = self.take_chunk(false) {
+                return Some(Ok(chunk));
+            }
+
+            // Flush at end of input, or once the maximum chunk size is reached
+            if self.is_eof || self.pending.len() >= self.max_chunk_size {
+                match self.take_chunk(true) {
+                    Some(chunk) => return Some(Ok(chunk)),
+                    None if self.is_eof => return None,
+                    None => continue,
+                }
+            }
+
+            match self.read_line() {
+                Ok(has_more) => self.is_eof = !has_more,
+                Err(e) => {
+                    self.is_eof = true;
+                    self.pending.clear();
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+impl<'a, R: BufRead> TokenStream<'a, R> {
+    /// Creates a new TokenStream over the reader, yielding the given unit of text.
+    pub fn new(
+        reader: R,
+        unit: StreamUnit,
+        tokenizer: &'a Tokenizer,
+        vocab: &'a VocabDatabase,
+    ) -> Self {
+        Self {
+            chunks: ChunkReader::new(reader, unit),
+            tokenizer,
+            vocab,
+            state: TokenizerState::default(),
+        }
+    }
+
+    /// Sets the maximum size in bytes of a single chunk, after which buffered input is tokenized even if incomplete.
+    pub fn max_chunk_size(mut self, size: usize) -> Self {
+        self.chunks.max_chunk_size = size.max(1);
+        self
+    }
+
+    /// Returns the byte offset within the stream of the most recently yielded chunk, which token spans are relative to.
+    pub fn offset(&self) -> usize {
+        self.chunks.offset()
+    }
+}
+
+impl<R: BufRead> Iterator for TokenStream<'_, R> {
+    type Item = Result<TokenizedInput, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = match self.chunks.next()? {
+            Ok(r) => r,
+            Err(e) => return Some(Err(e)),
+        };
+
+        Some(Ok(self.tokenizer.encode_with_state(
+            &chunk,
+            self.vocab,
+            &mut self.state,
+        )))
+    }
+}
+
+impl TryFrom<&str> for StreamUnit {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let res = match value.to_lowercase().as_str() {
+            "sentence" => Self::sentence,
+            "paragraph" => Self::paragraph,
+            _ => return Err(Error::Generic(format!("Invalid stream unit, {}", value))),
+        };
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunks(input: &str, unit: StreamUnit) -> Vec<String> {
+        ChunkReader::new(input.as_bytes(), unit).map(|chunk| chunk.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_sentences() {
+        let input = "This sentence is hard\nwrapped across two lines. Next one.\n\nA new paragraph";
+        assert_eq!(
+            chunks(input, StreamUnit::sentence),
+            vec![
+                "This sentence is hard\nwrapped across two lines.",
+                "Next one.",
+                "A new paragraph"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_paragraphs() {
+        let input = "First line\nof the first. Still first.\n\n-----\n\n\nSecond paragraph.\n";
+        assert_eq!(
+            chunks(input, StreamUnit::paragraph),
+            vec![
+                "First line\nof the first. Still first.",
+                "-----",
+                "Second paragraph."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_offsets() {
+        let input = "One here.  Two here.\n\nThree.";
+        let mut reader = ChunkReader::new(input.as_bytes(), StreamUnit::sentence);
+        while let Some(chunk) = reader.next() {
+            let chunk = chunk.unwrap();
+            assert_eq!(
+                &input[reader.offset()..reader.offset() + chunk.len()],
+                chunk
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_chunk_size() {
+        let mut reader =
+            ChunkReader::new("one two three four five".as_bytes(), StreamUnit::sentence);
+        reader.max_chunk_size = 8;
+        let res: Vec<String> = reader.map(|chunk| chunk.unwrap()).collect();
+        assert!(res.iter().all(|chunk| chunk.len() <= 8));
+        assert_eq!(res.join(" "), "one two three four five");
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let mut reader = ChunkReader::new(&b"hello \xff world"[..], StreamUnit::sentence);
+        assert!(reader.next().unwrap().is_err());
+    }
+}
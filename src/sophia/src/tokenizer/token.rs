@@ -49,6 +49,8 @@ pub struct Token {
     pub inner_value: String,
     #[serde(skip)]
     pub inner_unit: String,
+    #[serde(skip)]
+    pub span: Range<usize>,
 }
 
 /// Defines the type of a token, which can be a word, prefix, or suffix.
@@ -72,11 +74,14 @@ impl Token {
             Some(r) => r,
             None => return Self::unknown(query_word),
         };
-        let (_, token_id) = lookup.iter().next().unwrap();
+        let (_, token_id) = match lookup.iter().next() {
+            Some(r) => r,
+            None => return Self::unknown(query_word),
+        };
 
         // Get oken by id
         let mut token = Self::from_id(*token_id, vocab);
-        token.word = word;
+        token.word = vocab.user.format(*token_id, &word).unwrap_or(word);
         token.token_type = TokenType::word;
         token.potential_pos = lookup.keys().copied().collect();
 
@@ -124,10 +129,7 @@ impl Token {
 
     /// Creates a Token from a token ID using the vocabulary database, setting its index.
     pub fn from_id(token_id: i32, vocab: &VocabDatabase) -> Token {
-        let mut token = match vocab.words.id2token.get(&token_id) {
-            Some(r) => r.clone(),
-            None => Self::default(),
-        };
+        let mut token = vocab.get_token(token_id).unwrap_or_default();
         token.index = token_id;
 
         token
@@ -136,13 +138,14 @@ impl Token {
     /// Updates the POS tag of the Token, returning a new Token if the tag is valid in the vocabulary.
     pub fn update_pos(&self, pos_code: POSTag, vocab: &VocabDatabase) -> Option<Token> {
         // Get map
-        let index_map = vocab.words.wordlist.get(&self.word)?;
+        let index_map = vocab.get_pos_map(&self.word)?;
 
         // Get token id
         let index = index_map.get(&pos_code)?;
 
         // Return token
-        let token = Self::from_id(*index, vocab);
+        let mut token = Self::from_id(*index, vocab);
+        token.span = self.span.clone();
         Some(token)
     }
 
@@ -262,6 +265,11 @@ impl Token {
         self.pos == POSTag::PUNC
     }
 
+    /// Returns the lemma of the Token based on its POS tag, such as 'go' for 'went' or 'will have gone'.
+    pub fn lemma(&self, vocab: &VocabDatabase) -> String {
+        vocab.lemmatize(self)
+    }
+
     /// Retrieves the category vectors for the Token from the vocabulary database.
     pub fn get_category_vec(&self, vocab: &VocabDatabase) -> Vec<Vec<i16>> {
         let mut res: Vec<Vec<i16>> = Vec::new();
@@ -4,10 +4,13 @@
 // License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
 // Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
 
-use super::{MWE, Token, TokenCleaner, TokenizedInput};
+use super::unicode::{self, NormalizedText, UnicodeNormalization};
+use super::{MWE, StreamUnit, Token, TokenCleaner, TokenStream, TokenizedInput};
 use crate::pos_tagger::POSTag;
-use crate::vocab::{MWEType, VocabDatabase};
+use crate::vocab::{MWENode, MWEType, VocabDatabase};
 use regex::Regex;
+use std::io::BufRead;
+use std::ops::Range;
 
 static PREFIX_PAST_DATE_PERIOD_WORDS: &[&str] = &["last", "past", "prior"];
 static PREFIX_FUTURE_DATE_PERIOD_WORDS: &[&str] = &["in", "next", "within", "following"];
@@ -17,8 +20,14 @@ static PREFIX_PAST_DATE_WORDS: &[&str] = &["last", "past", "prior"];
 static PREFIX_FUTURE_DATE_WORDS: &[&str] = &["next", "following"];
 
 /// A tokenizer for converting input text into tokens, handling multi-word entities (MWEs) and special cases like contractions and dates.
+///
+/// Input is Unicode normalized before tokenization, and when `fold_diacritics` is enabled, words not found within the vocabulary are
+/// looked up again with diacritics removed (eg. José as jose), while the token retains its original surface form.
 #[derive(Default)]
-pub struct Tokenizer {}
+pub struct Tokenizer {
+    pub normalization: UnicodeNormalization,
+    pub fold_diacritics: bool,
+}
 
 /// A buffer for tokenization, storing output tokens, words, and state for handling MWEs, possessives, and special tags.
 #[derive(Default)]
@@ -33,24 +42,51 @@ pub struct Buffer {
     pub suffix: Vec<Token>,
     pub mwe_length: usize,
     pub mwe_scoring_length: usize,
+    pub spans: Vec<Range<usize>>,
+    pub span: Range<usize>,
+    pub is_negative: bool,
+    pub(crate) normalized: NormalizedText,
+}
+
+/// Tokenization state carried from the end of one chunk of input to the start of the next when streaming.
+#[derive(Default, Clone, Debug)]
+pub struct TokenizerState {
+    pub prev_tag: String,
+    pub is_possessive: bool,
+    pub is_negative: bool,
 }
 
 impl Tokenizer {
     /// Creates a new Tokenizer instance.
     pub fn new() -> Self {
-        Self {}
+        Self::default()
     }
 
     /// Encodes input text into a TokenizedInput, processing words, MWEs, and special tags using the vocabulary database.
     pub fn encode(&self, input: &str, vocab: &VocabDatabase) -> TokenizedInput {
+        self.encode_with_state(input, vocab, &mut TokenizerState::default())
+    }
+
+    /// Encodes input text continuing from the state left by the previous chunk, and updates the state for the next chunk.
+    pub fn encode_with_state(
+        &self,
+        input: &str,
+        vocab: &VocabDatabase,
+        state: &mut TokenizerState,
+    ) -> TokenizedInput {
         // Clean str
-        let clean_str = self.initial_clean(input);
+        let normalized = NormalizedText::new(input, self.normalization);
+        let clean_str = self.initial_clean(&normalized.text);
         let mut buffer = Buffer::new(input, &clean_str);
+        buffer.spans = self.align_words(&clean_str, &normalized.text);
+        buffer.normalized = normalized;
+        buffer.restore_state(state);
 
         // Go through words
         while !buffer.words.is_empty() {
             // Get next word
             let mut word = buffer.words.remove(0);
+            buffer.span = buffer.spans.remove(0);
             if buffer.words.is_empty() {
                 continue;
             }
@@ -74,6 +110,11 @@ impl Tokenizer {
                 token = Token::new(&word, vocab);
             }
 
+            // Diacritic folding
+            if token.pos == POSTag::FW && self.fold_diacritics {
+                token = self.lookup_folded(&word, token, vocab);
+            }
+
             // Check mwe
             self.check_mwe(&word, &token, vocab, &mut buffer);
 
@@ -91,27 +132,101 @@ impl Tokenizer {
         // Apply POS tagging
         vocab.words.pos_tagger.apply(&mut buffer.output, vocab);
 
+        *state = buffer.state();
         buffer.output
     }
 
-    /// Performs initial cleaning of input text, removing non-ASCII characters, leading symbols, and adding newline markers.
+    /// Streams input from a buffered reader, tokenizing one sentence or paragraph at a time with memory bounded to the current chunk.
+    pub fn encode_stream<'a, R: BufRead>(
+        &'a self,
+        reader: R,
+        unit: StreamUnit,
+        vocab: &'a VocabDatabase,
+    ) -> TokenStream<'a, R> {
+        TokenStream::new(reader, unit, self, vocab)
+    }
+
+    /// Performs initial cleaning of normalized input text, removing leading symbols other than hashtags and mentions, and adding newline markers.
     fn initial_clean(&self, input: &str) -> String {
         let re = Regex::new(r"^[\-\_\=\#\@\!]+").unwrap();
         let result = input
             .split("\n")
-            .map(|line| format!("{} |NL| ", re.replace(line, " ").trim()))
+            .map(|line| {
+                // Keep hashtags and mentions (eg. #launch, @alice)
+                let is_entity = line.starts_with(['#', '@'])
+                    && line.chars().nth(1).is_some_and(|c| c.is_alphanumeric());
+                match is_entity {
+                    true => format!("{} |NL| ", line.trim()),
+                    false => format!("{} |NL| ", re.replace(line, " ").trim()),
+                }
+            })
             .filter(|lc| !lc.is_empty())
             .collect::<Vec<String>>();
 
-        // Remove non-ascii asnd other control characters.  (needs removal / refinement upon support for multi-type char sets)
-        let re_non_ascii = Regex::new(r"[^\x20-\x7E]").unwrap();
-        let cleaned_str = re_non_ascii.replace_all(&result.join(" "), "").to_string();
+        // Collapse whitespace left by removed characters
+        let re_spaces = Regex::new(r" {2,}").unwrap();
+        let cleaned_str = re_spaces.replace_all(&result.join(" "), " ").to_string();
 
         cleaned_str.trim().to_string()
     }
 
+    /// Aligns each word of the cleaned text to its byte range within the normalized text, with newline markers aligned to the newline they replaced.
+    fn align_words(&self, cleaned_str: &str, normalized: &str) -> Vec<Range<usize>> {
+        let mut cursor = 0;
+        let mut spans: Vec<Range<usize>> = Vec::new();
+        for word in cleaned_str.split(" ") {
+            let search = if word == "|NL|" { "\n" } else { word };
+            match normalized[cursor..].find(search) {
+                Some(pos) if !word.is_empty() => {
+                    let start = cursor + pos;
+                    cursor = start + search.len();
+                    spans.push(start..cursor);
+                }
+                _ => {
+                    let pos = if word == "|NL|" {
+                        normalized.len()
+                    } else {
+                        cursor
+                    };
+                    spans.push(pos..pos);
+                }
+            }
+        }
+
+        spans
+    }
+
+    /// Looks up an unknown word with its diacritics folded, keeping the original surface form if found.
+    fn lookup_folded(&self, word: &str, token: Token, vocab: &VocabDatabase) -> Token {
+        let folded = unicode::fold_diacritics(word);
+        if folded == word {
+            return token;
+        }
+
+        let mut folded_token = Token::new(&folded, vocab);
+        if folded_token.pos == POSTag::FW {
+            return token;
+        }
+        folded_token.word = word.to_string();
+        folded_token
+    }
+
+    /// Retrieves the child node of an MWE trie node for a word, falling back to its diacritic folded form when enabled.
+    fn get_mwe_child<'a>(&self, node: &MWENode<'a>, word: &str) -> Option<MWENode<'a>> {
+        let word = word.to_lowercase();
+        node.get_child(&word).or_else(|| match self.fold_diacritics {
+            true => node.get_child(&unicode::fold_diacritics(&word)),
+            false => None,
+        })
+    }
+
     /// Pre-processes a word, cleaning it and handling special cases like contractions, numbers, and dates, updating the buffer.
     fn preprocess(&self, mut word: String, vocab: &VocabDatabase, buffer: &mut Buffer) -> String {
+        // Email addresses, URLs, phone numbers, hashtags and mentions, which cleaning would break apart
+        if self.push_entity(&word, vocab, buffer) {
+            return String::new();
+        }
+
         // Clean token
         let mut cleaner = TokenCleaner::new();
         word = match cleaner.clean(word, vocab, buffer) {
@@ -128,6 +243,7 @@ impl Tokenizer {
             let words: Vec<String> = value.split(" ").map(String::from).collect();
             for tmp_word in words.iter().skip(1).rev() {
                 buffer.words.insert(0, tmp_word.clone());
+                buffer.spans.insert(0, buffer.span.clone());
             }
             return words[0].clone();
         } else if buffer.prev_tag.as_str() == "|num|"
@@ -158,8 +274,26 @@ impl Tokenizer {
             return;
         }
 
+        // Check the user dictionary first, with its MWEs taking precedence over any overlapping within the vocabulary
+        let mwe_length = buffer.mwe_length;
+        self.scan_mwe(vocab.user.mwe_root(), word, token, vocab, buffer);
+        if mwe_length == 0 && buffer.mwe_length > 0 {
+            return;
+        }
+        self.scan_mwe(vocab.mwe_root(), word, token, vocab, buffer);
+    }
+
+    /// Scans the MWE trie from the given root for MWEs starting at the word, adding any found to the buffer.
+    fn scan_mwe(
+        &self,
+        root: MWENode,
+        word: &str,
+        token: &Token,
+        vocab: &VocabDatabase,
+        buffer: &mut Buffer,
+    ) {
         // Get first index
-        let mut index = match vocab.words.mwe.children.get(&word.to_lowercase()) {
+        let mut index = match self.get_mwe_child(&root, word) {
             Some(r) => r,
             None => return,
         };
@@ -171,18 +305,21 @@ impl Tokenizer {
 
         // Loop until we can't anymore
         let mut x = 0;
-        while let Some(next) = index.children.get(&buffer.words[x].to_lowercase().to_string()) {
-            if next.mwe_type == MWEType::standard || next.mwe_type == MWEType::both {
+        while x < buffer.words.len()
+            && let Some(next) = self.get_mwe_child(&index, &buffer.words[x])
+        {
+            let mwe_type = next.mwe_type();
+            if mwe_type == MWEType::standard || mwe_type == MWEType::both {
                 mwe.push(next.format(&buffer.words[x]));
-                if next.index > 0 {
-                    mwe_index = next.index;
+                if next.index() > 0 {
+                    mwe_index = next.index();
                 }
             }
 
-            if next.mwe_type == MWEType::scoring || next.mwe_type == MWEType::both {
+            if mwe_type == MWEType::scoring || mwe_type == MWEType::both {
                 mwe_scoring.push(next.format(&buffer.words[x]));
-                if next.index > 0 {
-                    mwe_scoring_index = next.index;
+                if next.index() > 0 {
+                    mwe_scoring_index = next.index();
                 }
             }
 
@@ -262,10 +399,27 @@ impl Buffer {
         }
     }
 
+    /// Restores the state carried from the previous chunk of input.
+    fn restore_state(&mut self, state: &TokenizerState) {
+        self.prev_tag = state.prev_tag.to_string();
+        self.is_possessive = state.is_possessive;
+        self.is_negative = state.is_negative;
+    }
+
+    /// Returns the state to carry into the next chunk of input, with any pending negation retained.
+    fn state(&self) -> TokenizerState {
+        TokenizerState {
+            prev_tag: self.prev_tag.to_string(),
+            is_possessive: self.is_possessive,
+            is_negative: self.is_negative || self.not_position.is_some(),
+        }
+    }
+
     /// Expands system tags for dates, times, and numerical suffixes, updating MWEs in the output and returning true if expanded.
     fn expand_system_tag(&mut self, token: &Token) -> bool {
         // Date / time period suffix
         let (mut res, mut period_word, mut period_tag) = (false, String::new(), String::new());
+        let mut period_span = token.span.clone();
         if ["|date_period|", "|time_period|"].contains(&self.prev_tag.as_str())
             && (SUFFIX_PAST_DATE_PERIOD_WORDS.contains(&token.word.as_str())
                 || SUFFIX_FUTURE_DATE_PERIOD_WORDS.contains(&token.word.as_str()))
@@ -275,6 +429,7 @@ impl Buffer {
                 self.output.tokens.last().unwrap().inner_word,
                 token.word
             );
+            period_span.start = self.output.tokens.last().unwrap().span.start;
             period_tag = if SUFFIX_PAST_DATE_PERIOD_WORDS.contains(&token.word.as_str()) {
                 format!("past_{}", self.prev_tag.trim_start_matches("|"))
             } else {
@@ -293,6 +448,9 @@ impl Buffer {
                 format!("|future_{}", token.word.trim_start_matches("|"))
             };
             mwe_token.inner_word = format!("{} {}", self.prev_tag, token.inner_word);
+            if let Some(prev) = self.output.tokens.last() {
+                mwe_token.span = prev.span.start..token.span.end;
+            }
             res = true;
 
             // Set MWE in output
@@ -307,10 +465,12 @@ impl Buffer {
             prev.word = token.word.to_string();
             prev.inner_word = format!("{} {}", prev.inner_word, token.inner_word);
             prev.inner_unit = token.inner_unit.to_string();
+            prev.span = prev.span.start..token.span.end;
             self.prev_tag = token.word.to_string();
             res = true;
 
             let inner_word = prev.inner_word.clone();
+            period_span = prev.span.clone();
             let length = self.output.tokens.len();
 
             // Check for time / date period prefix
@@ -321,6 +481,7 @@ impl Buffer {
                     || PREFIX_FUTURE_DATE_PERIOD_WORDS.contains(&chk_word.as_str())
                 {
                     period_word = format!("{} {}", chk_word, inner_word);
+                    period_span.start = self.output.tokens[length - 2].span.start;
                     period_tag = if PREFIX_PAST_DATE_PERIOD_WORDS.contains(&chk_word.as_str()) {
                         format!("|past_{}", token.word.trim_start_matches("|"))
                     } else {
@@ -339,6 +500,7 @@ impl Buffer {
         let mut mwe_token = self.output.tokens.last().unwrap().clone();
         mwe_token.word = period_tag;
         mwe_token.inner_word = period_word;
+        mwe_token.span = period_span;
 
         // Set MWE in output
         let mwe = self.output.mwe.last_mut().unwrap();
@@ -350,6 +512,10 @@ impl Buffer {
 
     /// Adds a token to the buffer, handling system tag expansion, MWEs, and properties like negation and possession.
     pub fn push_token(&mut self, mut token: Token) {
+        if token.span.is_empty() {
+            token.span = self.input_span();
+        }
+
         // Expand system tag
         if self.expand_system_tag(&token) {
             return;
@@ -358,7 +524,8 @@ impl Buffer {
         // Update token properties as necessary
         self.prev_tag = token.word.to_string();
         token.is_possessive = self.is_possessive;
-        token.is_negative = self.not_position.is_some() && (token.is_verb() || token.is_noun());
+        token.is_negative = (self.not_position.is_some() || self.is_negative)
+            && (token.is_verb() || token.is_noun());
 
         // Add have / had MWE if needed
         let mut mwe_added = false;
@@ -380,6 +547,7 @@ impl Buffer {
             self.not_position = None;
             self.have_position = None;
             self.had_position = None;
+            self.is_negative = false;
         } else if token.word.as_str() == "not" {
             self.not_position = Some(self.output.tokens.len());
         } else if token.word.as_str() == "have" || token.word.as_str() == "has" {
@@ -387,11 +555,13 @@ impl Buffer {
         } else if token.word.as_str() == "had" {
             self.had_position = Some(self.output.tokens.len());
         }
+        let span = token.span.clone();
         self.output.tokens.push(token);
 
         // Update mwe as needed
         if self.mwe_length > 0 {
             self.mwe_length -= 1;
+            extend_mwe_span(&mut self.output.mwe, &span);
         } else if !mwe_added {
             self.output.mwe.push(MWE {
                 position: self.output.tokens.len() - 1,
@@ -402,6 +572,7 @@ impl Buffer {
         // Update scoring mwe as needed
         if self.mwe_scoring_length > 0 {
             self.mwe_scoring_length -= 1;
+            extend_mwe_span(&mut self.output.mwe_scoring, &span);
         } else {
             self.output.mwe_scoring.push(MWE {
                 position: self.output.tokens.len() - 1,
@@ -427,6 +598,7 @@ impl Buffer {
         // Define new token
         let mut token = verb_token.clone();
         token.word = format!("{} {}", phrase.join(" "), verb_token.word);
+        token.span = self.output.tokens[start].span.start..verb_token.span.end;
 
         // Set label
         if self.have_position.is_some() {
@@ -496,6 +668,7 @@ impl Buffer {
     pub fn add_future_verb(&mut self, mut token: Token, phrase: &[String]) {
         token.word = phrase.join(" ");
         token.is_negative = phrase.contains(&"not".to_string());
+        token.span = self.input_span();
         token.pos = if token.pos == POSTag::VB {
             POSTag::VF
         } else {
@@ -524,6 +697,7 @@ impl Buffer {
         //return;
         //}
         token.word = mwe.join(" ");
+        token.span = self.input_span();
         self.mwe_length = mwe.len();
 
         // Add not / have token, if needed
@@ -541,10 +715,27 @@ impl Buffer {
         }
     }
 
+    /// Returns the span of text at the byte offset within the current word, or the span of the entire word if the text was altered during cleaning.
+    ///
+    /// Spans within the buffer are byte ranges of the normalized input, and are converted via `input_span()` when assigned to tokens.
+    pub fn sub_span(&self, offset: usize, text: &str) -> Range<usize> {
+        let span = self.span.start + offset..self.span.start + offset + text.len();
+        match self.normalized.text.get(span.clone()) {
+            Some(normalized) if normalized == text && span.end <= self.span.end => span,
+            _ => self.span.clone(),
+        }
+    }
+
+    /// Returns the byte range of the current word within the original input.
+    pub fn input_span(&self) -> Range<usize> {
+        self.normalized.input_span(&self.span)
+    }
+
     /// Adds a scoring MWE to the output, using the provided index and phrase.
     pub fn add_mwe_scoring(&mut self, index: &i32, mwe: &[String], vocab: &VocabDatabase) {
         let mut token = Token::from_id(*index, vocab);
         token.word = mwe.join(" ");
+        token.span = self.input_span();
         self.mwe_scoring_length = mwe.len();
 
         self.output.mwe_scoring.push(MWE {
@@ -553,3 +744,54 @@ impl Buffer {
         });
     }
 }
+
+/// Extends the span of the most recent MWE to also cover the given span.
+fn extend_mwe_span(mwe: &mut [MWE], span: &Range<usize>) {
+    if let Some(token) = mwe.iter_mut().rev().find_map(|entry| entry.token.as_mut()) {
+        token.span = token.span.start.min(span.start)..token.span.end.max(span.end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::fixtures;
+    use crate::vocab::{USER_TOKEN_ID_START, UserWord};
+
+    /// Returns the words and token IDs of the MWEs found within the input.
+    fn mwe_words(mwe: &[MWE]) -> Vec<(String, i32)> {
+        mwe.iter()
+            .filter_map(|entry| entry.token.as_ref())
+            .map(|token| (token.word.to_string(), token.index))
+            .collect()
+    }
+
+    #[test]
+    fn test_user_mwe_overlap() {
+        let mut vocab = fixtures::vocab();
+        let tokenizer = Tokenizer::new();
+        let base_index = vocab.words.mwe.get("motor vehicle");
+
+        // Standard and scoring MWEs from the vocabulary
+        let output = tokenizer.encode("a motor vehicle", &vocab);
+        let expected = vec![("motor vehicle".to_string(), base_index)];
+        assert_eq!(mwe_words(&output.mwe), expected);
+        assert_eq!(mwe_words(&output.mwe_scoring), expected);
+
+        // A user MWE starting with the same words replaces both
+        let user_index =
+            vocab.add_user_word(&UserWord::new("motor vehicle accident", POSTag::NN)).unwrap();
+        assert!(user_index >= USER_TOKEN_ID_START);
+
+        let output = tokenizer.encode("a motor vehicle accident", &vocab);
+        assert_eq!(
+            mwe_words(&output.mwe),
+            vec![("motor vehicle accident".to_string(), user_index)]
+        );
+        assert!(mwe_words(&output.mwe_scoring).is_empty());
+
+        // The vocabulary is still checked when the user MWE does not match
+        let output = tokenizer.encode("a motor vehicle", &vocab);
+        assert_eq!(mwe_words(&output.mwe), expected);
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+use super::{SentenceSplitter, Token, TokenizedInput, Tokenizer};
+use crate::pos_tagger::POSTag;
+use crate::vocab::{MWENode, MWEType, VocabDatabase};
+use std::ops::Range;
+
+/// The result of truecasing input text, containing the corrected text and every word that was changed.
+#[derive(Default, Clone, Debug)]
+pub struct TruecasedInput {
+    pub original: String,
+    pub output: String,
+    pub changes: Vec<TruecaseChange>,
+}
+
+/// A single word whose capitalization was changed, with its byte range within the original input.
+#[derive(Clone, Debug)]
+pub struct TruecaseChange {
+    pub position: usize,
+    pub span: Range<usize>,
+    pub original: String,
+    pub corrected: String,
+    pub reason: TruecaseReason,
+}
+
+/// The reason the capitalization of a word was changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TruecaseReason {
+    sentence_start,
+    vocabulary,
+    mwe,
+    proper_noun,
+    lowercase,
+}
+
+/// A single word within the input, being the byte range of the word with surrounding punctuation excluded.
+struct Word {
+    span: Range<usize>,
+    lower: String,
+    is_sentence_start: bool,
+}
+
+impl Tokenizer {
+    /// Restores the canonical capitalization of input text, such as chat messages or voice transcripts typed in all lowercase or all uppercase.
+    ///
+    /// Canonical casing is taken from the vocabulary and MWEs, with unknown words predicted as proper nouns title cased, and the first
+    /// word of each sentence capitalized.  For input already in mixed case, only words written entirely in lowercase are changed.
+    pub fn truecase(&self, input: &str, vocab: &VocabDatabase) -> TruecasedInput {
+        let is_mixed_case =
+            input.chars().any(|c| c.is_lowercase()) && input.chars().any(|c| c.is_uppercase());
+        let words = Self::split_words(input);
+
+        // Tag parts of speech to distinguish senses (eg. may / May) and predict proper nouns
+        let mut tokenized = TokenizedInput::new(input);
+        tokenized.tokens = words
+            .iter()
+            .map(|word| Token {
+                span: word.span.clone(),
+                ..Token::new(&word.lower, vocab)
+            })
+            .collect();
+        vocab.words.pos_tagger.apply(&mut tokenized, vocab);
+
+        let mut res = TruecasedInput {
+            original: input.to_string(),
+            ..Default::default()
+        };
+        let mut corrected: Vec<(String, TruecaseReason)> = Vec::new();
+
+        let mut x = 0;
+        while x < words.len() {
+            // MWEs, checking the user dictionary first
+            let mwe = [vocab.user.mwe_root(), vocab.mwe_root()]
+                .into_iter()
+                .find_map(|root| Self::match_mwe(root, &words[x..], vocab));
+            if let Some(formatted) = mwe {
+                x += formatted.len();
+                corrected.extend(formatted.into_iter().map(|word| (word, TruecaseReason::mwe)));
+                continue;
+            }
+
+            corrected.push(Self::truecase_word(
+                &words[x].lower,
+                &tokenized.tokens[x],
+                vocab,
+            ));
+            x += 1;
+        }
+
+        // Build output
+        let mut last = 0;
+        for (position, (word, (mut casing, mut reason))) in words.iter().zip(corrected).enumerate()
+        {
+            let original = &input[word.span.clone()];
+            res.output.push_str(&input[last..word.span.start]);
+            last = word.span.end;
+
+            if word.is_sentence_start {
+                let capitalized = capitalize(&casing);
+                if capitalized != casing {
+                    (casing, reason) = (capitalized, TruecaseReason::sentence_start);
+                }
+            }
+            if (is_mixed_case && original.chars().any(|c| c.is_uppercase())) || casing == original {
+                res.output.push_str(original);
+                continue;
+            }
+
+            res.output.push_str(&casing);
+            res.changes.push(TruecaseChange {
+                position,
+                span: word.span.clone(),
+                original: original.to_string(),
+                corrected: casing,
+                reason,
+            });
+        }
+        res.output.push_str(&input[last..]);
+
+        res
+    }
+
+    /// Splits input into words, excluding surrounding punctuation and flagging the first word of each sentence.
+    fn split_words(input: &str) -> Vec<Word> {
+        let sentences = SentenceSplitter::new().split(input);
+        let mut words: Vec<Word> = Vec::new();
+
+        let mut offset = 0;
+        for part in input.split_inclusive(char::is_whitespace) {
+            let start = offset;
+            offset += part.len();
+
+            let trimmed = part.trim_end();
+            let leading =
+                trimmed.len() - trimmed.trim_start_matches(|c: char| !c.is_alphanumeric()).len();
+            let core = trimmed.trim_matches(|c: char| !c.is_alphanumeric());
+            if core.is_empty() {
+                continue;
+            }
+
+            // First word within its sentence
+            let span = start + leading..start + leading + core.len();
+            let is_sentence_start = sentences.iter().any(|sentence| {
+                sentence.span.contains(&span.start)
+                    && !words.last().is_some_and(|last| sentence.span.contains(&last.span.start))
+            });
+
+            words.push(Word {
+                span,
+                lower: core.to_lowercase(),
+                is_sentence_start,
+            });
+        }
+
+        words
+    }
+
+    /// Matches the longest standard MWE starting at the first word, returning each word formatted in its canonical casing.
+    ///
+    /// Proper noun MWEs without any stored capitalization are title cased.
+    fn match_mwe(root: MWENode, words: &[Word], vocab: &VocabDatabase) -> Option<Vec<String>> {
+        let mut node = root;
+        let (mut formatted, mut res): (Vec<String>, Option<Vec<String>>) = (Vec::new(), None);
+
+        for word in words.iter() {
+            node = match node.get_child(&word.lower) {
+                Some(r) => r,
+                None => break,
+            };
+            formatted.push(node.format(&word.lower));
+
+            if node.index() > 0 && formatted.len() > 1 && node.mwe_type() != MWEType::scoring {
+                res = Some(formatted.clone());
+                if let Some(token) = vocab.get_token(node.index())
+                    && (token.pos == POSTag::NNP || token.pos == POSTag::NNPS)
+                    && formatted.iter().all(|part| !part.chars().any(|c| c.is_uppercase()))
+                {
+                    res = Some(formatted.iter().map(|part| capitalize(part)).collect());
+                }
+            }
+        }
+
+        res
+    }
+
+    /// Returns the canonical casing of a single word, from its vocabulary entry or predicted part of speech.
+    fn truecase_word(word: &str, token: &Token, vocab: &VocabDatabase) -> (String, TruecaseReason) {
+        // Canonical casing, unless the token was replaced by a spelling correction
+        if token.index > 0
+            && let Some(canonical) = vocab.get_token(token.index)
+            && canonical.word.to_lowercase() == word
+        {
+            if let Some(capitalization) = vocab.words.capitalization.get(&token.index) {
+                return (
+                    capitalization.apply(word, &canonical.word),
+                    TruecaseReason::vocabulary,
+                );
+            }
+            if let Some(formatted) = vocab.user.format(token.index, word) {
+                return (formatted, TruecaseReason::vocabulary);
+            }
+            if token.pos != POSTag::NNP && token.pos != POSTag::NNPS {
+                return (word.to_string(), TruecaseReason::lowercase);
+            }
+        }
+
+        if token.pos == POSTag::NNP || token.pos == POSTag::NNPS {
+            return (capitalize(word), TruecaseReason::proper_noun);
+        }
+        (word.to_string(), TruecaseReason::lowercase)
+    }
+}
+
+/// Uppercases the first character of a word, leaving the remainder unchanged.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
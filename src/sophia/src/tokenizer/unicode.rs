@@ -0,0 +1,128 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+use crate::error::Error;
+use std::ops::Range;
+use unicode_normalization::UnicodeNormalization as _;
+use unicode_normalization::char::is_combining_mark;
+
+/// The Unicode normalization form applied to input text before tokenization.
+///
+/// NFKC additionally folds compatibility characters such as ligatures, full-width forms and superscripts into their plain equivalents.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnicodeNormalization {
+    none,
+    nfc,
+    #[default]
+    nfkc,
+}
+
+/// Input text normalized and with typographic punctuation mapped, along with the byte range within the input each of its bytes originates from.
+#[derive(Default, Clone)]
+pub(crate) struct NormalizedText {
+    pub text: String,
+    offsets: Vec<Range<usize>>,
+    input_len: usize,
+}
+
+/// Normalizes input text into the given Unicode normalization form.
+pub(crate) fn normalize(input: &str, form: UnicodeNormalization) -> String {
+    match form {
+        UnicodeNormalization::none => input.to_string(),
+        UnicodeNormalization::nfc => input.nfc().collect(),
+        UnicodeNormalization::nfkc => input.nfkc().collect(),
+    }
+}
+
+/// Maps typographic punctuation to its ASCII equivalent, converts all whitespace other than newlines to a regular space, and removes control characters.
+pub(crate) fn map_punctuation(input: &str) -> String {
+    let mut res = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '\n' => res.push('\n'),
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' | '\u{02BC}' => {
+                res.push('\'')
+            }
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' | '\u{00AB}'
+            | '\u{00BB}' => res.push('"'),
+            '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}'
+            | '\u{2212}' => res.push('-'),
+            '\u{2026}' => res.push_str("..."),
+            '\u{2022}' | '\u{00B7}' => res.push('*'),
+            '\u{200B}' => res.push(' '),
+            '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}' => {}
+            _ if c.is_whitespace() => res.push(' '),
+            _ if c.is_control() => {}
+            _ => res.push(c),
+        }
+    }
+
+    res
+}
+
+/// Folds diacritics from a word (eg. José to Jose), leaving all other characters unchanged.
+pub(crate) fn fold_diacritics(word: &str) -> String {
+    word.nfd().filter(|c| !is_combining_mark(*c)).nfc().collect()
+}
+
+impl NormalizedText {
+    /// Normalizes the input, processing each character along with any combining marks that follow it so composed characters remain aligned to their source.
+    pub fn new(input: &str, form: UnicodeNormalization) -> Self {
+        let mut res = Self {
+            text: String::with_capacity(input.len()),
+            offsets: Vec::new(),
+            input_len: input.len(),
+        };
+
+        let mut chars = input.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            let mut end = start + c.len_utf8();
+            while let Some((pos, next)) = chars.peek()
+                && is_combining_mark(*next)
+            {
+                end = pos + next.len_utf8();
+                chars.next();
+            }
+
+            let mapped = map_punctuation(&normalize(&input[start..end], form));
+            res.offsets.extend(std::iter::repeat_n(start..end, mapped.len()));
+            res.text.push_str(&mapped);
+        }
+
+        res
+    }
+
+    /// Converts a byte range within the normalized text into the byte range within the input it originates from.
+    pub fn input_span(&self, span: &Range<usize>) -> Range<usize> {
+        let start = self.offsets.get(span.start).map(|r| r.start).unwrap_or(self.input_len);
+        if span.is_empty() {
+            return start..start;
+        }
+
+        let end = self.offsets.get(span.end - 1).map(|r| r.end).unwrap_or(self.input_len);
+        start..end.max(start)
+    }
+}
+
+impl TryFrom<&str> for UnicodeNormalization {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let res = match value.to_lowercase().as_str() {
+            "none" => Self::none,
+            "nfc" => Self::nfc,
+            "nfkc" => Self::nfkc,
+            _ => {
+                return Err(Error::Generic(format!(
+                    "Invalid Unicode normalization form, {}",
+                    value
+                )));
+            }
+        };
+
+        Ok(res)
+    }
+}
@@ -0,0 +1,972 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+use super::{
+    FutureVerbPhrases, MWEType, PhraseIntent, PhraseIntents, Pronoun, SpellChecker,
+    SpellCheckerCohort, SpellCheckerCohortPOS, SpellCheckerCohortSize, SpellCheckerEntry,
+    UserDictionary, VocabCache, VocabCategory, VocabCategoryDatabase, VocabCategoryIndex,
+    VocabDatabase, VocabDatabaseMeta, VocabMWE, VocabPreProcessDatabase, VocabWordDatabase,
+};
+use crate::error::Error;
+use crate::pos_tagger::{HMM, POSTag, POSTagger};
+use crate::tokenizer::{EntityType, Token};
+use crate::vocab::mwe::Capitalization;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+static CATEGORY_ROOTS: &[&str] = &["nouns", "verbs", "adverbs", "adjectives", "ner"];
+static PRONOUN_TAGS: &[POSTag] = &[POSTag::PR, POSTag::PRP, POSTag::WPR, POSTag::WPRP];
+
+/// Builds a complete vocabulary data store from plain-text source material.
+///
+/// All sources are tab delimited with one entry per line, and blank lines or lines starting with `#` are ignored.
+///
+/// - `categories.txt`: one full category path per line, such as `verbs/action/search/retrieve`.
+/// - `words.txt`: `word  POS  [key=value ...]`, listed in order of frequency.  Supported keys are `stem`, `potential_stem`,
+///   `categories`, `synonyms`, `hypernyms`, `hyponyms`, `plural` and `flags` (stop_word, auxillary_verb, predicative_verb, infinitive_prefix, name).
+/// - `mwe.txt`: same layout as `words.txt`, plus an optional `type` key of standard, scoring or both.
+/// - `pronouns.txt`: `word  category  sub_category  gender  person  number`
+/// - `hashes.txt`: `word  tag  value`, such as `can't  |contraction|  can not`
+/// - `future_verbs.txt`: one phrase per line with the verb as a POS tag, such as `going to VB`
+/// - `phrase_intents.txt`: `intent  phrase`
+///
+/// Values within keys are comma delimited.  Word references may be restricted to a POS tag (eg. `run/VB`), and
+/// category paths under `ner/` are assigned as named entity labels.  System tags of entities recognized by the tokenizer
+/// (eg. `|email|`, `|url|`) are added automatically when not listed within words.
+pub struct VocabBuilder {
+    language: String,
+    author: String,
+    comment: String,
+    categories: Vec<String>,
+    entries: Vec<BuilderEntry>,
+    pronouns: Vec<(String, Pronoun)>,
+    hashes: HashMap<String, (String, String)>,
+    future_verbs: Vec<String>,
+    phrase_intents: Vec<(PhraseIntent, String)>,
+    pos_tagger: Option<POSTagger>,
+}
+
+/// A single parsed line from a word or MWE source.
+struct BuilderEntry {
+    source: String,
+    line: usize,
+    word: String,
+    pos: POSTag,
+    mwe_type: Option<MWEType>,
+    attributes: HashMap<String, String>,
+}
+
+impl VocabBuilder {
+    /// Creates a new, empty VocabBuilder for the specified language.
+    pub fn new(language: &str) -> Self {
+        Self {
+            language: language.to_string(),
+            author: String::new(),
+            comment: String::new(),
+            categories: Vec::new(),
+            entries: Vec::new(),
+            pronouns: Vec::new(),
+            hashes: HashMap::new(),
+            future_verbs: Vec::new(),
+            phrase_intents: Vec::new(),
+            pos_tagger: None,
+        }
+    }
+
+    /// Creates a VocabBuilder from a directory of source files, using the file names listed within the struct documentation.
+    pub fn from_dir(dir: &str, language: &str) -> Result<Self, Error> {
+        let mut builder = Self::new(language);
+
+        if let Some(contents) = Self::read_source(dir, "categories.txt")? {
+            builder.add_categories(&contents)?;
+        }
+        match Self::read_source(dir, "words.txt")? {
+            Some(contents) => builder.add_words(&contents)?,
+            None => {
+                return Err(Error::Build(format!(
+                    "No words.txt file exists within {}",
+                    dir
+                )));
+            }
+        };
+        if let Some(contents) = Self::read_source(dir, "mwe.txt")? {
+            builder.add_mwes(&contents)?;
+        }
+        if let Some(contents) = Self::read_source(dir, "pronouns.txt")? {
+            builder.add_pronouns(&contents)?;
+        }
+        if let Some(contents) = Self::read_source(dir, "hashes.txt")? {
+            builder.add_hashes(&contents)?;
+        }
+        if let Some(contents) = Self::read_source(dir, "future_verbs.txt")? {
+            builder.add_future_verbs(&contents)?;
+        }
+        if let Some(contents) = Self::read_source(dir, "phrase_intents.txt")? {
+            builder.add_phrase_intents(&contents)?;
+        }
+
+        Ok(builder)
+    }
+
+    /// Sets the author stored within the data store metadata.
+    pub fn set_author(&mut self, author: &str) {
+        self.author = author.to_string();
+    }
+
+    /// Sets the comment stored within the data store metadata.
+    pub fn set_comment(&mut self, comment: &str) {
+        self.comment = comment.to_string();
+    }
+
+    /// Sets a trained POS tagger to include, otherwise an untrained HMM is used.
+    pub fn set_pos_tagger(&mut self, pos_tagger: POSTagger) {
+        self.pos_tagger = Some(pos_tagger);
+    }
+
+    /// Adds category paths, one full path per line.
+    pub fn add_categories(&mut self, contents: &str) -> Result<(), Error> {
+        for (line, parts) in Self::parse_lines(contents) {
+            Self::check_category_path(parts[0], "categories", line)?;
+            self.categories.push(parts[0].to_lowercase());
+        }
+        Ok(())
+    }
+
+    /// Adds single words with their POS tag and optional attributes.
+    pub fn add_words(&mut self, contents: &str) -> Result<(), Error> {
+        for (line, parts) in Self::parse_lines(contents) {
+            let entry = BuilderEntry::parse("words", line, &parts)?;
+            if entry.word.contains(" ") {
+                return Err(Error::Build(format!(
+                    "Multi-word entity '{}' on line {} of words, add it as an MWE instead",
+                    entry.word, line
+                )));
+            }
+            self.entries.push(entry);
+        }
+        Ok(())
+    }
+
+    /// Adds multi-word entities with their POS tag and optional attributes.
+    pub fn add_mwes(&mut self, contents: &str) -> Result<(), Error> {
+        for (line, parts) in Self::parse_lines(contents) {
+            let mut entry = BuilderEntry::parse("mwe", line, &parts)?;
+            if !entry.word.contains(" ") {
+                return Err(Error::Build(format!(
+                    "Single word '{}' on line {} of mwe, add it as a word instead",
+                    entry.word, line
+                )));
+            }
+
+            let mwe_type = match entry.attributes.remove("type") {
+                Some(value) => MWEType::try_from(value.as_str())?,
+                None => MWEType::standard,
+            };
+            entry.mwe_type = Some(mwe_type);
+            self.entries.push(entry);
+        }
+        Ok(())
+    }
+
+    /// Adds pronouns with their category, sub-category, gender, person and number.
+    pub fn add_pronouns(&mut self, contents: &str) -> Result<(), Error> {
+        for (line, parts) in Self::parse_lines(contents) {
+            if parts.len() < 6 {
+                return Err(Error::Build(format!(
+                    "Expected six columns on line {} of pronouns",
+                    line
+                )));
+            }
+
+            let pronoun = Pronoun {
+                category: parts[1].try_into()?,
+                sub_category: parts[2].try_into()?,
+                gender: parts[3].try_into()?,
+                person: parts[4].try_into()?,
+                number: parts[5].try_into()?,
+            };
+            self.pronouns.push((parts[0].to_lowercase(), pronoun));
+        }
+        Ok(())
+    }
+
+    /// Adds pre-processing hashes, each consisting of a word, system tag and value.
+    pub fn add_hashes(&mut self, contents: &str) -> Result<(), Error> {
+        for (line, parts) in Self::parse_lines(contents) {
+            if parts.len() < 2 {
+                return Err(Error::Build(format!(
+                    "Expected a tag on line {} of hashes",
+                    line
+                )));
+            }
+            let value = parts.get(2).unwrap_or(&"").to_string();
+            self.hashes.insert(parts[0].to_lowercase(), (parts[1].to_string(), value));
+        }
+        Ok(())
+    }
+
+    /// Adds future verb phrases, one per line.
+    pub fn add_future_verbs(&mut self, contents: &str) -> Result<(), Error> {
+        for (line, parts) in Self::parse_lines(contents) {
+            if !parts[0].split(" ").any(|word| word.starts_with("V")) {
+                return Err(Error::Build(format!(
+                    "No verb POS tag within future verb phrase on line {}",
+                    line
+                )));
+            }
+            self.future_verbs.push(parts[0].to_string());
+        }
+        Ok(())
+    }
+
+    /// Adds phrase intents, each consisting of an intent and phrase.
+    pub fn add_phrase_intents(&mut self, contents: &str) -> Result<(), Error> {
+        for (line, parts) in Self::parse_lines(contents) {
+            if parts.len() < 2 {
+                return Err(Error::Build(format!(
+                    "Expected a phrase on line {} of phrase intents",
+                    line
+                )));
+            }
+            let intent = PhraseIntent::from_str(parts[0])?;
+            self.phrase_intents.push((intent, parts[1].to_string()));
+        }
+        Ok(())
+    }
+
+    /// Builds the vocabulary data store from all sources added.
+    pub fn build(mut self) -> Result<VocabDatabase, Error> {
+        self.add_entity_tags();
+        let categories = self.build_categories()?;
+        let mut words = self.build_words(&categories)?;
+
+        // POS tagger
+        words.pos_tagger = match self.pos_tagger.take() {
+            Some(r) => r,
+            None => POSTagger {
+                hmm: HMM::new(),
+                ..Default::default()
+            },
+        };
+        words.pos_tagger.hmm.vocab_size = words.wordlist.len() as f32;
+        let preprocess = self.build_preprocess();
+
+        // Phrase intents
+        for (intent, phrase) in self.phrase_intents.iter() {
+            let tokens = self.resolve_phrase(phrase, &words)?;
+            words.phrase_intents.insert(*intent, &tokens);
+        }
+
+        let mut vocab = VocabDatabase {
+            meta: VocabDatabaseMeta::new(&self.language, &self.author, &self.comment),
+            preprocess,
+            words,
+            categories,
+            cache: VocabCache::default(),
+            user: UserDictionary::new(),
+        };
+        vocab.index_category_words();
+
+        Ok(vocab)
+    }
+
+    /// Builds the vocabulary data store, and saves it to the specified file.
+    pub fn save(self, filename: &str) -> Result<(), Error> {
+        let mut vocab = self.build()?;
+        vocab.save(filename)
+    }
+
+    /// Builds the vocabulary data store, and saves it to the specified file with zstd compression (eg. en.dat.zst).
+    pub fn save_compressed(self, filename: &str, level: i32) -> Result<(), Error> {
+        let mut vocab = self.build()?;
+        vocab.save_compressed(filename, level)
+    }
+
+    /// Adds the system tags of entities recognized by the tokenizer (eg. |email|), unless already present within words.
+    fn add_entity_tags(&mut self) {
+        for entity_type in EntityType::all() {
+            let tag = entity_type.tag();
+            if self.entries.iter().any(|entry| entry.word == tag && entry.pos == POSTag::SYS) {
+                continue;
+            }
+
+            self.entries.push(BuilderEntry {
+                source: "words".to_string(),
+                line: 0,
+                word: tag.to_string(),
+                pos: POSTag::SYS,
+                mwe_type: None,
+                attributes: HashMap::new(),
+            });
+        }
+    }
+
+    /// Builds the category trees, assigning IDs in depth-first order so every sub-tree forms a contiguous range.
+    fn build_categories(&self) -> Result<VocabCategoryDatabase, Error> {
+        let mut db = VocabCategoryDatabase::default();
+
+        // Gather paths, including those only referenced by words
+        let mut paths = self.categories.clone();
+        for entry in self.entries.iter() {
+            for path in entry.list("categories") {
+                Self::check_category_path(&path, &entry.source, entry.line)?;
+                paths.push(path.to_lowercase());
+            }
+        }
+
+        // Insert paths
+        for path in paths.iter() {
+            let (root, remaining_path) = path.split_once("/").unwrap();
+            let index = match root {
+                "nouns" => &mut db.nouns,
+                "verbs" => &mut db.verbs,
+                "adverbs" => &mut db.adverbs,
+                "adjectives" => &mut db.adjectives,
+                _ => &mut db.ner,
+            };
+            index.insert(remaining_path, 0);
+        }
+
+        // Assign IDs
+        let mut counter: i16 = 0;
+        let mut nodes: HashMap<i16, VocabCategory> = HashMap::new();
+        for index in [
+            &mut db.nouns,
+            &mut db.verbs,
+            &mut db.adverbs,
+            &mut db.adjectives,
+            &mut db.ner,
+        ] {
+            Self::assign_category_ids(index, &[], &mut counter, &mut nodes)?;
+        }
+
+        db.counter = counter;
+        db.nodes = nodes;
+        Ok(db)
+    }
+
+    /// Recursively assigns IDs to the children of a category index, creating their category nodes.
+    fn assign_category_ids(
+        index: &mut VocabCategoryIndex,
+        fqn: &[i16],
+        counter: &mut i16,
+        nodes: &mut HashMap<i16, VocabCategory>,
+    ) -> Result<(), Error> {
+        for (name, child) in index.children.iter_mut() {
+            *counter = match counter.checked_add(1) {
+                Some(r) => r,
+                None => {
+                    return Err(Error::Build(
+                        "Too many categories, maximum is 32767".to_string(),
+                    ));
+                }
+            };
+            child.index = *counter;
+
+            let mut child_fqn = fqn.to_vec();
+            child_fqn.push(child.index);
+            Self::assign_category_ids(child, &child_fqn, counter, nodes)?;
+
+            let cat = VocabCategory {
+                depth: child_fqn.len() as i8,
+                fqn: child_fqn,
+                name: name.to_string(),
+                children: child.children.iter().map(|(n, c)| (n.to_string(), c.index)).collect(),
+                pos: String::new(),
+                words: Vec::new(),
+            };
+            nodes.insert(child.index, cat);
+        }
+
+        Ok(())
+    }
+
+    /// Builds the word database, assigning token IDs and resolving all word references.
+    fn build_words(&self, categories: &VocabCategoryDatabase) -> Result<VocabWordDatabase, Error> {
+        let mut words = VocabWordDatabase {
+            wordlist: HashMap::new(),
+            pos_tagger: POSTagger::new(),
+            mwe: VocabMWE::default(),
+            capitalization: HashMap::new(),
+            future_verbs: FutureVerbPhrases::new(None),
+            phrase_intents: PhraseIntents::new(),
+            id2token: HashMap::new(),
+            plural: HashMap::new(),
+            mapped: None,
+            stem_index: OnceLock::new(),
+            plural_index: OnceLock::new(),
+            search_index: OnceLock::new(),
+            mwe_index: OnceLock::new(),
+        };
+
+        // Assign token IDs
+        for (x, entry) in self.entries.iter().enumerate() {
+            let index = (x + 1) as i32;
+            let key = entry.word.to_lowercase();
+
+            if let Some(mwe_type) = &entry.mwe_type {
+                if words.mwe.get(&key) > 0 {
+                    return Err(entry.error("duplicate MWE"));
+                }
+                words.mwe.insert(&entry.word, index, mwe_type.clone());
+            } else {
+                let pos_map = words.wordlist.entry(key).or_default();
+                if pos_map.contains_key(&entry.pos) {
+                    return Err(entry.error("duplicate word and POS tag"));
+                }
+                pos_map.insert(entry.pos, index);
+
+                let capitalization = VocabMWE::classify_capitalization(&entry.word);
+                if capitalization != Capitalization::lower {
+                    words.capitalization.insert(index, capitalization);
+                }
+            }
+
+            let token = Token {
+                word: entry.word.to_string(),
+                stem: index,
+                pos: entry.pos,
+                ..Default::default()
+            };
+            words.id2token.insert(index, token);
+        }
+
+        // Resolve attributes
+        for (x, entry) in self.entries.iter().enumerate() {
+            let index = (x + 1) as i32;
+            let mut token = words.id2token.get(&index).unwrap().clone();
+
+            for path in entry.list("categories") {
+                let category_id = categories.path2id(&path).unwrap();
+                if path.to_lowercase().starts_with("ner/") {
+                    token.ner.push(category_id);
+                } else {
+                    token.categories.push(category_id);
+                }
+            }
+
+            if let Some(stem) = entry.attributes.get("stem") {
+                token.stem = self.resolve_word(stem, entry, &words)?;
+            }
+            token.potential_stem = self.resolve_list(entry, "potential_stem", &words)?;
+            token.synonyms = self.resolve_list(entry, "synonyms", &words)?;
+            token.hypernyms = self.resolve_list(entry, "hypernyms", &words)?;
+            token.hyponyms = self.resolve_list(entry, "hyponyms", &words)?;
+
+            if let Some(plural) = entry.attributes.get("plural") {
+                let plural_index = self.resolve_word(plural, entry, &words)?;
+                words.plural.insert(index, plural_index);
+            }
+
+            for flag in entry.list("flags") {
+                match flag.as_str() {
+                    "name" => token.is_name = true,
+                    "stop_word" | "auxillary_verb" | "predicative_verb" | "infinitive_prefix" => {}
+                    _ => return Err(entry.error(&format!("invalid flag '{}'", flag))),
+                }
+            }
+            words.id2token.insert(index, token);
+        }
+
+        // Pronouns
+        for (word, pronoun) in self.pronouns.iter() {
+            let pos_map = match words.wordlist.get(word) {
+                Some(r) => r,
+                None => {
+                    return Err(Error::Build(format!(
+                        "Pronoun '{}' does not exist within words",
+                        word
+                    )));
+                }
+            };
+
+            let indexes: Vec<i32> = pos_map
+                .iter()
+                .filter(|(tag, _)| PRONOUN_TAGS.contains(tag))
+                .map(|(_, index)| *index)
+                .collect();
+            if indexes.is_empty() {
+                return Err(Error::Build(format!(
+                    "Pronoun '{}' is not listed with a pronoun POS tag within words",
+                    word
+                )));
+            }
+
+            for index in indexes {
+                words.id2token.get_mut(&index).unwrap().pronoun = Some(pronoun.clone());
+            }
+        }
+
+        // Future verbs
+        for phrase in self.future_verbs.iter() {
+            words.future_verbs.insert(phrase);
+        }
+
+        Ok(words)
+    }
+
+    /// Builds the pre-processing database, including hashes, word lists and spell checker cohorts.
+    fn build_preprocess(&self) -> VocabPreProcessDatabase {
+        let mut preprocess = VocabPreProcessDatabase {
+            hashes: self.hashes.clone(),
+            spellchecker: SpellChecker::default(),
+            future_verb_prefixes: Vec::new(),
+            stop_words: Vec::new(),
+            predicative_verbs: Vec::new(),
+            auxillary_verbs: Vec::new(),
+            infinitive_prefixes: Vec::new(),
+        };
+
+        // Future verb prefixes
+        for phrase in self.future_verbs.iter() {
+            let prefix = phrase.split(" ").next().unwrap().to_lowercase();
+            if !preprocess.future_verb_prefixes.contains(&prefix) {
+                preprocess.future_verb_prefixes.push(prefix);
+            }
+        }
+
+        for (x, entry) in self.entries.iter().enumerate() {
+            let index = (x + 1) as i32;
+
+            // Word lists
+            for flag in entry.list("flags") {
+                match flag.as_str() {
+                    "stop_word" => preprocess.stop_words.push(index),
+                    "auxillary_verb" => preprocess.auxillary_verbs.push(index),
+                    "predicative_verb" => preprocess.predicative_verbs.push(index),
+                    "infinitive_prefix" => preprocess.infinitive_prefixes.push(index),
+                    _ => {}
+                }
+            }
+
+            // Spell checker, in order of frequency
+            if entry.mwe_type.is_some()
+                || entry.pos.is_punctuation()
+                || [POSTag::SYS, POSTag::SYM].contains(&entry.pos)
+                || !entry.word.chars().all(|c| c.is_alphabetic())
+            {
+                continue;
+            }
+            let cohort = SpellCheckerCohort {
+                pos: SpellCheckerCohortPOS::from(entry.pos),
+                length: SpellCheckerCohortSize::from(entry.word.len()),
+            };
+            preprocess.spellchecker.cohorts.entry(cohort).or_default().push(SpellCheckerEntry {
+                word_index: index,
+                ..Default::default()
+            });
+        }
+
+        preprocess
+    }
+
+    /// Resolves a phrase into tokens, preferring the longest MWE at each position.
+    fn resolve_phrase(&self, phrase: &str, words: &VocabWordDatabase) -> Result<Vec<Token>, Error> {
+        let parts: Vec<&str> = phrase.split_whitespace().collect();
+        let mut tokens: Vec<Token> = Vec::new();
+
+        let mut x = 0;
+        while x < parts.len() {
+            // Check for MWE
+            let (mut index, mut length) = (0, 1);
+            let mut node = &words.mwe;
+            for (offset, part) in parts[x..].iter().enumerate() {
+                node = match node.children.get(&part.to_lowercase()) {
+                    Some(next) => next.as_ref(),
+                    None => break,
+                };
+                if node.index > 0 && offset > 0 {
+                    (index, length) = (node.index, offset + 1);
+                }
+            }
+
+            // Single word
+            if index == 0 {
+                index = match self.lookup_reference(parts[x], words) {
+                    Some(r) => r,
+                    None => {
+                        return Err(Error::Build(format!(
+                            "Unknown word '{}' within phrase intent '{}'",
+                            parts[x], phrase
+                        )));
+                    }
+                };
+            }
+
+            tokens.push(Token {
+                index,
+                ..Default::default()
+            });
+            x += length;
+        }
+
+        Ok(tokens)
+    }
+
+    /// Resolves a comma delimited list of word references for the given attribute.
+    fn resolve_list(
+        &self,
+        entry: &BuilderEntry,
+        key: &str,
+        words: &VocabWordDatabase,
+    ) -> Result<Vec<i32>, Error> {
+        let mut res: Vec<i32> = Vec::new();
+        for reference in entry.list(key) {
+            res.push(self.resolve_word(&reference, entry, words)?);
+        }
+        Ok(res)
+    }
+
+    /// Resolves a single word reference into its token ID, returning an error if it does not exist.
+    fn resolve_word(
+        &self,
+        reference: &str,
+        entry: &BuilderEntry,
+        words: &VocabWordDatabase,
+    ) -> Result<i32, Error> {
+        match self.lookup_reference(reference, words) {
+            Some(r) => Ok(r),
+            None => Err(entry.error(&format!("unknown word reference '{}'", reference))),
+        }
+    }
+
+    /// Looks up a word reference (eg. `run` or `run/VB`) within the wordlist and MWEs.
+    fn lookup_reference(&self, reference: &str, words: &VocabWordDatabase) -> Option<i32> {
+        let (word, pos) = match reference.rsplit_once("/") {
+            Some((word, tag)) if POSTag::from_str(tag).to_str() == tag => {
+                (word, Some(POSTag::from_str(tag)))
+            }
+            _ => (reference, None),
+        };
+        let word = word.trim().to_lowercase();
+
+        // Single word
+        if let Some(pos_map) = words.wordlist.get(&word) {
+            return match pos {
+                Some(tag) => pos_map.get(&tag).copied(),
+                None => pos_map.values().next().copied(),
+            };
+        }
+
+        // MWE
+        let index = words.mwe.get(&word);
+        if index == 0 {
+            return None;
+        }
+        match pos {
+            Some(tag) if words.id2token.get(&index)?.pos != tag => None,
+            _ => Some(index),
+        }
+    }
+
+    /// Ensures a category path starts with a valid root and has at least one category.
+    fn check_category_path(path: &str, source: &str, line: usize) -> Result<(), Error> {
+        match path.to_lowercase().split_once("/") {
+            Some((root, name)) if CATEGORY_ROOTS.contains(&root) && !name.is_empty() => Ok(()),
+            _ => Err(Error::Build(format!(
+                "Invalid category path '{}' on line {} of {}",
+                path, line, source
+            ))),
+        }
+    }
+
+    /// Splits source contents into tab delimited lines, skipping blank lines and comments.
+    fn parse_lines(contents: &str) -> Vec<(usize, Vec<&str>)> {
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with("#"))
+            .map(|(x, line)| (x + 1, line.split("\t").map(|part| part.trim()).collect()))
+            .collect()
+    }
+
+    /// Reads a source file within the directory, returning None if it does not exist.
+    fn read_source(dir: &str, filename: &str) -> Result<Option<String>, Error> {
+        let path = format!("{}/{}", dir, filename);
+        if !Path::new(&path).exists() {
+            return Ok(None);
+        }
+        Ok(Some(fs::read_to_string(&path)?))
+    }
+}
+
+impl BuilderEntry {
+    /// Parses a word or MWE line, consisting of the word, POS tag and optional key=value attributes.
+    fn parse(source: &str, line: usize, parts: &[&str]) -> Result<Self, Error> {
+        if parts.len() < 2 || parts[0].is_empty() {
+            return Err(Error::Build(format!(
+                "Expected a word and POS tag on line {} of {}",
+                line, source
+            )));
+        }
+
+        let pos = POSTag::from_str(parts[1]);
+        if pos.to_str() != parts[1].to_uppercase() {
+            return Err(Error::Build(format!(
+                "Invalid POS tag '{}' on line {} of {}",
+                parts[1], line, source
+            )));
+        }
+
+        let mut attributes: HashMap<String, String> = HashMap::new();
+        for part in parts[2..].iter().filter(|part| !part.is_empty()) {
+            let (key, value) = match part.split_once("=") {
+                Some(r) => r,
+                None => {
+                    return Err(Error::Build(format!(
+                        "Invalid attribute '{}' on line {} of {}, expected key=value",
+                        part, line, source
+                    )));
+                }
+            };
+            attributes.insert(key.trim().to_lowercase(), value.trim().to_string());
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            line,
+            word: parts[0].to_string(),
+            pos,
+            mwe_type: None,
+            attributes,
+        })
+    }
+
+    /// Returns the comma delimited values of an attribute, or an empty vector if not present.
+    fn list(&self, key: &str) -> Vec<String> {
+        match self.attributes.get(key) {
+            Some(value) => {
+                value.split(",").map(|v| v.trim().to_string()).filter(|v| !v.is_empty()).collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Creates a build error referencing the entry's source and line number.
+    fn error(&self, message: &str) -> Error {
+        Error::Build(format!(
+            "Word '{}' on line {} of {}, {}",
+            self.word, self.line, self.source, message
+        ))
+    }
+}
+
+impl Default for VocabBuilder {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::fixtures::{self, TempDir};
+
+    /// Writes the sample sources into a temporary directory, with comments and blank lines prepended to the words.
+    fn write_sources() -> TempDir {
+        let dir = TempDir::new("builder");
+        let words = format!("# word\tPOS\tattributes\n\n{}", fixtures::WORDS);
+        for (filename, contents) in [
+            ("categories.txt", fixtures::CATEGORIES),
+            ("words.txt", words.as_str()),
+            ("mwe.txt", fixtures::MWES),
+            ("pronouns.txt", fixtures::PRONOUNS),
+            ("hashes.txt", fixtures::HASHES),
+            ("future_verbs.txt", fixtures::FUTURE_VERBS),
+            ("phrase_intents.txt", fixtures::PHRASE_INTENTS),
+        ] {
+            fs::write(dir.file(filename), contents).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_from_dir() {
+        let dir = write_sources();
+        let vocab = VocabBuilder::from_dir(dir.path(), "en").unwrap().build().unwrap();
+
+        // Token IDs follow the order of words, then MWEs
+        let word_count = fixtures::WORDS.lines().count();
+        for (x, line) in fixtures::WORDS.lines().enumerate() {
+            let parts: Vec<&str> = line.split("\t").collect();
+            let pos_map = vocab.words.wordlist.get(parts[0]).unwrap();
+            assert_eq!(
+                pos_map.get(&POSTag::from_str(parts[1])),
+                Some(&((x + 1) as i32))
+            );
+            assert_eq!(
+                vocab.words.id2token.get(&((x + 1) as i32)).unwrap().word,
+                parts[0]
+            );
+        }
+        for (x, line) in fixtures::MWES.lines().enumerate() {
+            let phrase = line.split("\t").next().unwrap();
+            assert_eq!(vocab.words.mwe.get(phrase), (word_count + x + 1) as i32);
+        }
+
+        // POS maps retain the order words were listed in
+        let run: Vec<(POSTag, i32)> =
+            vocab.words.wordlist.get("run").unwrap().iter().map(|(tag, id)| (*tag, *id)).collect();
+        let run_vb = fixtures::WORDS
+            .lines()
+            .position(|line| line == "run\tVB\tcategories=verbs/action/movement");
+        assert_eq!(
+            run,
+            vec![
+                (POSTag::VB, run_vb.unwrap() as i32 + 1),
+                (POSTag::NN, run_vb.unwrap() as i32 + 2)
+            ]
+        );
+
+        // Attributes resolve to token IDs
+        let id =
+            |word: &str, tag: POSTag| *vocab.words.wordlist.get(word).unwrap().get(&tag).unwrap();
+        let car = vocab.words.id2token.get(&id("car", POSTag::NN)).unwrap();
+        assert_eq!(car.stem, id("car", POSTag::NN));
+        assert_eq!(car.synonyms, vec![id("automobile", POSTag::NN)]);
+        assert_eq!(car.hypernyms, vec![id("vehicle", POSTag::NN)]);
+        assert_eq!(
+            vocab.words.plural.get(&id("car", POSTag::NN)),
+            Some(&id("cars", POSTag::NNS))
+        );
+        assert_eq!(
+            vocab.words.id2token.get(&id("went", POSTag::VBD)).unwrap().stem,
+            id("go", POSTag::VB)
+        );
+        assert_eq!(
+            vocab.words.id2token.get(&id("matt", POSTag::NNP)).unwrap().ner,
+            vec![6]
+        );
+        assert!(vocab.words.id2token.get(&id("matt", POSTag::NNP)).unwrap().is_name);
+        assert!(vocab.words.id2token.get(&id("he", POSTag::PRP)).unwrap().pronoun.is_some());
+
+        // Hashes and system tags of recognized entities
+        assert_eq!(
+            vocab.preprocess.hashes.get("can't"),
+            Some(&("|contraction|".to_string(), "can not".to_string()))
+        );
+        assert!(vocab.words.wordlist.get("|email|").unwrap().contains_key(&POSTag::SYS));
+    }
+
+    #[test]
+    fn test_category_ids() {
+        let dir = write_sources();
+        let vocab = VocabBuilder::from_dir(dir.path(), "en").unwrap().build().unwrap();
+        let categories = &vocab.categories;
+
+        // Pre-order, so every sub-tree forms a contiguous range of IDs
+        for (path, id) in [
+            ("nouns/transportation", 1),
+            ("nouns/transportation/automobile", 2),
+            ("nouns/transportation/aircraft", 3),
+            ("verbs/action", 4),
+            ("verbs/action/movement", 5),
+            ("ner/person", 6),
+        ] {
+            assert_eq!(categories.path2id(path), Some(id), "{}", path);
+        }
+        assert_eq!(categories.counter, 6);
+
+        let automobile = categories.get(&2).unwrap();
+        assert_eq!(automobile.name, "automobile");
+        assert_eq!(automobile.fqn, vec![1, 2]);
+        assert_eq!(automobile.depth, 2);
+
+        let transportation = categories.get(&1).unwrap();
+        assert_eq!(transportation.fqn, vec![1]);
+        let children: Vec<(&str, i16)> =
+            transportation.children.iter().map(|(name, id)| (name.as_str(), *id)).collect();
+        assert_eq!(children, vec![("automobile", 2), ("aircraft", 3)]);
+
+        // Categories referenced by words are assigned IDs, with NER categories kept separate
+        let truck = vocab.words.wordlist.get("truck").unwrap().get(&POSTag::NN).unwrap();
+        assert_eq!(vocab.words.id2token.get(truck).unwrap().categories, vec![2]);
+        assert!(vocab.words.id2token.get(truck).unwrap().ner.is_empty());
+    }
+
+    #[test]
+    fn test_mwes() {
+        let dir = write_sources();
+        let vocab = VocabBuilder::from_dir(dir.path(), "en").unwrap().build().unwrap();
+        let mwe = &vocab.words.mwe;
+
+        // Nested phrases share trie nodes
+        let new = mwe.children.get("new").unwrap();
+        assert_eq!(new.index, 0);
+        let york = new.children.get("york").unwrap();
+        assert_eq!(york.index, mwe.get("new york"));
+        assert_eq!(
+            york.children.get("city").unwrap().index,
+            mwe.get("new york city")
+        );
+        assert!(york.mwe_type == MWEType::standard);
+
+        let vehicle = mwe.children.get("motor").unwrap().children.get("vehicle").unwrap();
+        assert!(vehicle.mwe_type == MWEType::both);
+        assert_eq!(vehicle.orig_word, "vehicle");
+
+        // MWE tokens, and word references to MWEs
+        let token = vocab.words.id2token.get(&vehicle.index).unwrap();
+        assert_eq!(token.word, "motor vehicle");
+        assert_eq!(token.pos, POSTag::NN);
+        assert_eq!(
+            token.hypernyms,
+            vec![*vocab.words.wordlist.get("vehicle").unwrap().get(&POSTag::NN).unwrap()]
+        );
+        let truck = vocab.words.wordlist.get("truck").unwrap().get(&POSTag::NN).unwrap();
+        assert_eq!(
+            vocab.words.id2token.get(truck).unwrap().hypernyms,
+            vec![vehicle.index]
+        );
+        assert_eq!(mwe.get("york city"), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let dir = write_sources();
+
+        // Line numbers count comments and blank lines
+        fs::write(dir.file("words.txt"), "# comment\n\nthe\tDT\ncar\tXYZ\n").unwrap();
+        let err = VocabBuilder::from_dir(dir.path(), "en").err().unwrap().to_string();
+        assert!(
+            err.contains("Invalid POS tag 'XYZ' on line 4 of words"),
+            "{}",
+            err
+        );
+
+        fs::write(dir.file("words.txt"), "car\tNN\tplural\n").unwrap();
+        let err = VocabBuilder::from_dir(dir.path(), "en").err().unwrap().to_string();
+        assert!(
+            err.contains("Invalid attribute 'plural' on line 1 of words"),
+            "{}",
+            err
+        );
+
+        // References and categories of words are resolved when built
+        fs::write(dir.file("words.txt"), "car\tNN\tplural=cars\n").unwrap();
+        fs::remove_file(dir.file("mwe.txt")).unwrap();
+        fs::remove_file(dir.file("pronouns.txt")).unwrap();
+        fs::remove_file(dir.file("phrase_intents.txt")).unwrap();
+        let err =
+            VocabBuilder::from_dir(dir.path(), "en").unwrap().build().err().unwrap().to_string();
+        assert!(err.contains("unknown word reference 'cars'"), "{}", err);
+
+        fs::write(dir.file("words.txt"), "car\tNN\tcategories=animals/dog\n").unwrap();
+        let err =
+            VocabBuilder::from_dir(dir.path(), "en").unwrap().build().err().unwrap().to_string();
+        assert!(
+            err.contains("Invalid category path 'animals/dog' on line 1 of words"),
+            "{}",
+            err
+        );
+
+        fs::remove_file(dir.file("words.txt")).unwrap();
+        let err = VocabBuilder::from_dir(dir.path(), "en").err().unwrap().to_string();
+        assert!(err.contains("No words.txt file exists"), "{}", err);
+    }
+}
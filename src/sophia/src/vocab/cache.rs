@@ -4,59 +4,456 @@
 // License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
 // Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
 
-use crate::error::Error;
+use crate::error::{Error, LoadError};
 use bincode;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::Path;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
-/// A cache for storing vocabulary-related data, such as typos, to improve processing efficiency.
-#[derive(Serialize, Deserialize, Default)]
+/// The number of independently locked shards, allowing concurrent lookups from multiple threads.
+const SHARD_COUNT: usize = 16;
+
+/// The default maximum number of typo corrections retained, with the least recently used evicted first.
+pub const DEFAULT_CACHE_CAPACITY: usize = 100_000;
+
+/// The default minimum interval between automatic flushes to disk.
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Counter giving each temporary file written by this process a unique name.
+static TMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A persistent cache of learned typo corrections, split into shards that are each bounded by least recently used eviction.
+///
+/// Changes are flushed to `cache.dat` within the vocabulary directory periodically as typos are added, on a background
+/// thread so tokenization is not blocked, and again upon drop.
 pub struct VocabCache {
-    pub typos: HashMap<String, String>,
+    shards: Vec<Mutex<CacheShard>>,
+    capacity: usize,
+    filename: Option<PathBuf>,
+    flush_interval: Duration,
+    last_flush: Mutex<Instant>,
+    dirty: AtomicBool,
+    writer: Mutex<Option<JoinHandle<Result<(), Error>>>>,
+}
+
+/// A single shard of the cache, with each typo stamped with the tick of its last use.
+#[derive(Default)]
+struct CacheShard {
+    typos: HashMap<String, (String, u64)>,
+    recency: BTreeMap<u64, String>,
+    tick: u64,
 }
 
 impl VocabCache {
-    /// Loads the vocabulary cache from a file in the specified directory, returning a default cache if the file does not exist.
+    /// Creates a new, empty cache with the default capacity which is never flushed to disk.
+    pub fn new() -> Self {
+        Self {
+            shards: (0..SHARD_COUNT).map(|_| Mutex::new(CacheShard::default())).collect(),
+            capacity: DEFAULT_CACHE_CAPACITY,
+            filename: None,
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+            last_flush: Mutex::new(Instant::now()),
+            dirty: AtomicBool::new(false),
+            writer: Mutex::new(None),
+        }
+    }
+
+    /// Loads the vocabulary cache from a file in the specified directory, returning an empty cache if the file does not exist.
+    ///
+    /// The returned cache is flushed back to the same file.
     pub fn load(vocab_dir: &str) -> Result<VocabCache, Error> {
-        let cache_file = format!("{}/cache.dat", vocab_dir);
-        if !Path::new(&cache_file).exists() {
-            return Ok(Self::default());
+        let mut cache = Self::new();
+        let cache_file = Path::new(vocab_dir).join("cache.dat");
+        cache.filename = Some(cache_file.clone());
+        if !cache_file.exists() {
+            return Ok(cache);
         }
 
+        // Encoded identically to the previous map of typos, so older cache files still load
         let encoded = fs::read(&cache_file)?;
-        let cache: VocabCache = match bincode::deserialize(&encoded[..]) {
+        let typos: Vec<(String, String)> = match bincode::deserialize(&encoded[..]) {
             Ok(r) => r,
             Err(e) => {
-                return Err(Error::Load(format!(
+                return Err(Error::Load(LoadError::Corrupt(format!(
                     "Unable to load vocabulary cache, {}",
                     e
-                )));
+                ))));
             }
         };
 
+        for (original, correct) in typos {
+            cache.shard(&original).insert(original, correct, cache.shard_capacity());
+        }
         Ok(cache)
     }
 
-    /// Saves the vocabulary cache to a file in the specified directory using bincode serialization.
+    /// Saves the vocabulary cache to a file in the specified directory, atomically replacing any existing file.
     pub fn save(&self, vocab_dir: &str) -> Result<(), Error> {
-        let cache_file = format!("{}/cache.dat", vocab_dir);
-        let encoded = match bincode::serialize(&self) {
-            Ok(r) => r,
-            Err(e) => {
-                return Err(Error::Save(format!(
-                    "Unable to serialize vocabulary cache, {}",
-                    e
-                )));
-            }
+        write_typos(&Path::new(vocab_dir).join("cache.dat"), &self.typos())
+    }
+
+    /// Flushes the cache to the file it was loaded from, if it has changed since the last flush, waiting for any background write to finish.
+    pub fn flush(&self) -> Result<(), Error> {
+        *lock(&self.last_flush) = Instant::now();
+        let Some(filename) = &self.filename else {
+            return Ok(());
         };
-        fs::write(&cache_file, &encoded)?;
-        Ok(())
+
+        let mut writer = lock(&self.writer);
+        if let Some(handle) = writer.take() {
+            self.join_writer(handle);
+        }
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            return Ok(());
+        }
+
+        // Flag as changed again so the next flush retries
+        let res = write_typos(filename, &self.typos());
+        if res.is_err() {
+            self.dirty.store(true, Ordering::Release);
+        }
+        res
+    }
+
+    /// Gets the correction of a typo, marking it as recently used.
+    pub fn get_typo(&self, original: &str) -> Option<String> {
+        self.shard(original).get(original)
     }
 
     /// Adds a typo mapping to the cache, associating the original word with its correct form.
-    pub fn add_typo(&mut self, original: &str, correct: &str) {
-        self.typos.insert(original.to_string(), correct.to_string());
+    ///
+    /// Evicts the least recently used typo if the cache is full, and flushes to disk if the flush interval has elapsed.
+    pub fn add_typo(&self, original: &str, correct: &str) {
+        let capacity = self.shard_capacity();
+        self.shard(original).insert(original.to_string(), correct.to_string(), capacity);
+        self.dirty.store(true, Ordering::Release);
+
+        if lock(&self.last_flush).elapsed() >= self.flush_interval {
+            self.flush_background();
+        }
+    }
+
+    /// Returns all typo mappings, ordered from least to most recently used within each shard.
+    pub fn typos(&self) -> Vec<(String, String)> {
+        self.shards.iter().flat_map(|shard| lock(shard).entries()).collect()
+    }
+
+    /// Sets the maximum number of typo corrections retained, evicting the least recently used as needed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let shard_capacity = self.shard_capacity();
+        for shard in self.shards.iter() {
+            lock(shard).evict(shard_capacity);
+        }
+    }
+
+    /// Sets the minimum interval between automatic flushes to disk.
+    pub fn set_flush_interval(&mut self, interval: Duration) {
+        self.flush_interval = interval;
+    }
+
+    /// Returns the number of typo corrections within the cache.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| lock(shard).typos.len()).sum()
+    }
+
+    /// Checks whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Flushes the cache on a background thread if it has changed, skipping the flush while a previous write is still in progress.
+    fn flush_background(&self) {
+        *lock(&self.last_flush) = Instant::now();
+        let Some(filename) = self.filename.clone() else {
+            return;
+        };
+
+        let mut writer = lock(&self.writer);
+        if writer.as_ref().is_some_and(|handle| !handle.is_finished()) {
+            return;
+        }
+        if let Some(handle) = writer.take() {
+            self.join_writer(handle);
+        }
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            return;
+        }
+
+        let typos = self.typos();
+        *writer = Some(thread::spawn(move || write_typos(&filename, &typos)));
+    }
+
+    /// Waits for a background write to finish, flagging the cache as changed if it failed so the next flush retries.
+    fn join_writer(&self, handle: JoinHandle<Result<(), Error>>) {
+        if !matches!(handle.join(), Ok(Ok(()))) {
+            self.dirty.store(true, Ordering::Release);
+        }
+    }
+
+    /// Returns the locked shard holding the given word.
+    fn shard(&self, word: &str) -> MutexGuard<'_, CacheShard> {
+        lock(&self.shards[shard_index(word)])
+    }
+
+    /// Returns the maximum number of typo corrections retained by each shard.
+    fn shard_capacity(&self) -> usize {
+        self.capacity.div_ceil(SHARD_COUNT)
+    }
+}
+
+impl Default for VocabCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for VocabCache {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Returns the index of the shard holding the given word.
+fn shard_index(word: &str) -> usize {
+    let mut hasher = DefaultHasher::new();
+    word.hash(&mut hasher);
+    hasher.finish() as usize % SHARD_COUNT
+}
+
+/// Locks the mutex, recovering the guard if a thread panicked while holding it.
+///
+/// Every lock guards plain data which is never left partially updated, and the cache is flushed upon drop, which must not panic.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Writes typo corrections to a uniquely named temporary file in the same directory, then renames it over the given filename.
+///
+/// The temporary file is named by process ID and a counter, so processes sharing a vocabulary directory never write to the same file.
+fn write_typos(filename: &Path, typos: &[(String, String)]) -> Result<(), Error> {
+    let encoded = match bincode::serialize(typos) {
+        Ok(r) => r,
+        Err(e) => {
+            return Err(Error::Save(format!(
+                "Unable to serialize vocabulary cache, {}",
+                e
+            )));
+        }
+    };
+
+    let name = filename.file_name().and_then(|name| name.to_str()).unwrap_or("cache.dat");
+    let tmp_file = filename.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        name,
+        process::id(),
+        TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    fs::write(&tmp_file, &encoded)?;
+    if let Err(e) = fs::rename(&tmp_file, filename) {
+        let _ = fs::remove_file(&tmp_file);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+impl CacheShard {
+    /// Gets a typo correction, stamping it as the most recently used.
+    fn get(&mut self, original: &str) -> Option<String> {
+        self.tick += 1;
+        let (correct, tick) = self.typos.get_mut(original)?;
+        self.recency.remove(tick);
+        *tick = self.tick;
+        self.recency.insert(self.tick, original.to_string());
+        Some(correct.to_string())
+    }
+
+    /// Inserts a typo correction as the most recently used, then evicts down to the capacity.
+    fn insert(&mut self, original: String, correct: String, capacity: usize) {
+        self.tick += 1;
+        if let Some((_, tick)) = self.typos.insert(original.clone(), (correct, self.tick)) {
+            self.recency.remove(&tick);
+        }
+        self.recency.insert(self.tick, original);
+        self.evict(capacity);
+    }
+
+    /// Evicts the least recently used typo corrections until within the capacity.
+    fn evict(&mut self, capacity: usize) {
+        while self.typos.len() > capacity {
+            let Some((_, original)) = self.recency.pop_first() else {
+                break;
+            };
+            self.typos.remove(&original);
+        }
+    }
+
+    /// Returns all typo corrections, ordered from least to most recently used.
+    fn entries(&self) -> Vec<(String, String)> {
+        self.recency
+            .values()
+            .filter_map(|original| {
+                self.typos
+                    .get(original)
+                    .map(|(correct, _)| (original.to_string(), correct.to_string()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::fixtures::TempDir;
+
+    /// Returns the given number of distinct words that all belong to the same shard.
+    fn same_shard(count: usize) -> Vec<String> {
+        let shard = shard_index("typo0");
+        (0..)
+            .map(|x| format!("typo{}", x))
+            .filter(|word| shard_index(word) == shard)
+            .take(count)
+            .collect()
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        // Three typos per shard
+        let mut cache = VocabCache::new();
+        cache.set_capacity(3 * SHARD_COUNT);
+        let words = same_shard(5);
+
+        for word in words[..3].iter() {
+            cache.add_typo(word, "correct");
+        }
+        assert_eq!(cache.get_typo(&words[0]), Some("correct".to_string()));
+
+        // Least recently used is the second, as the first was just read
+        cache.add_typo(&words[3], "correct");
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.get_typo(&words[1]), None);
+
+        // Re-adding a typo refreshes it, leaving the third least recently used
+        cache.add_typo(&words[0], "fixed");
+        cache.add_typo(&words[4], "correct");
+        assert_eq!(cache.get_typo(&words[2]), None);
+        assert_eq!(cache.get_typo(&words[0]), Some("fixed".to_string()));
+
+        let typos: Vec<String> = cache.typos().into_iter().map(|(original, _)| original).collect();
+        assert_eq!(
+            typos,
+            vec![words[3].clone(), words[4].clone(), words[0].clone()]
+        );
+
+        // Shrinking the capacity evicts down to a single typo per shard
+        cache.set_capacity(1);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get_typo(&words[0]), Some("fixed".to_string()));
+    }
+
+    #[test]
+    fn test_sharding() {
+        let mut cache = VocabCache::new();
+        cache.set_capacity(100 * SHARD_COUNT);
+
+        // Shards are filled concurrently, each bounded independently
+        thread::scope(|scope| {
+            for x in 0..4 {
+                let cache = &cache;
+                scope.spawn(move || {
+                    for y in 0..500 {
+                        cache.add_typo(&format!("typo{}-{}", x, y), &format!("word{}", y));
+                    }
+                });
+            }
+        });
+
+        let sizes: Vec<usize> = cache.shards.iter().map(|shard| lock(shard).typos.len()).collect();
+        assert!(sizes.iter().all(|&size| size > 0 && size <= 100));
+        assert_eq!(cache.len(), sizes.iter().sum::<usize>());
+        assert_eq!(cache.typos().len(), cache.len());
+
+        // Each typo resides within the shard of its word
+        for (index, shard) in cache.shards.iter().enumerate() {
+            assert!(lock(shard).typos.keys().all(|word| shard_index(word) == index));
+        }
+    }
+
+    #[test]
+    fn test_flush_reload() {
+        let dir = TempDir::new("cache-flush");
+        let cache = VocabCache::load(dir.path()).unwrap();
+        assert!(cache.is_empty());
+
+        cache.add_typo("teh", "the");
+        cache.add_typo("recieve", "receive");
+        cache.flush().unwrap();
+        drop(cache);
+
+        // Written via a temporary file, which is renamed over cache.dat
+        let files: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(files, vec!["cache.dat".to_string()]);
+
+        let loaded = VocabCache::load(dir.path()).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.get_typo("teh"), Some("the".to_string()));
+        assert_eq!(loaded.get_typo("recieve"), Some("receive".to_string()));
+
+        // Flushed again upon drop, including by the background writer
+        let mut loaded = loaded;
+        loaded.set_flush_interval(Duration::ZERO);
+        loaded.add_typo("wierd", "weird");
+        drop(loaded);
+
+        let reloaded = VocabCache::load(dir.path()).unwrap();
+        assert_eq!(reloaded.len(), 3);
+        assert_eq!(reloaded.get_typo("wierd"), Some("weird".to_string()));
+
+        // A corrupt cache file is reported rather than loaded
+        fs::write(dir.file("cache.dat"), [0xFF; 3]).unwrap();
+        assert!(matches!(
+            VocabCache::load(dir.path()),
+            Err(Error::Load(LoadError::Corrupt(_)))
+        ));
+    }
+
+    #[test]
+    fn test_poisoned_locks() {
+        let dir = TempDir::new("cache-poisoned");
+        let cache = VocabCache::load(dir.path()).unwrap();
+        cache.add_typo("teh", "the");
+
+        // Poison every lock by panicking while holding it
+        thread::scope(|scope| {
+            let res = scope
+                .spawn(|| {
+                    let _writer = lock(&cache.writer);
+                    let _last_flush = lock(&cache.last_flush);
+                    let _shards: Vec<_> = cache.shards.iter().map(lock).collect();
+                    panic!("poisoned");
+                })
+                .join();
+            assert!(res.is_err());
+        });
+        assert!(cache.writer.is_poisoned() && cache.last_flush.is_poisoned());
+
+        // Flushing, background flushes and drop all still write the cache
+        cache.flush().unwrap();
+        cache.add_typo("wierd", "weird");
+        cache.flush_background();
+        drop(cache);
+
+        let loaded = VocabCache::load(dir.path()).unwrap();
+        assert_eq!(loaded.len(), 2);
     }
 }
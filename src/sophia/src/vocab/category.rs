@@ -4,7 +4,7 @@
 // License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
 // Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
 
-use super::VocabDatabase;
+use super::UserDictionary;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -21,6 +21,8 @@ pub struct VocabCategoryDatabase {
     pub adverbs: VocabCategoryIndex,
     pub adjectives: VocabCategoryIndex,
     pub ner: VocabCategoryIndex,
+    #[serde(skip)]
+    pub(crate) word_index: HashMap<i16, Vec<i32>>,
 }
 
 /// A trie-like index for vocabulary categories, mapping paths to category indices and their children.
@@ -45,38 +47,83 @@ pub struct VocabCategory {
 
 impl VocabCategoryDatabase {
     /// Get category by path name
-    pub fn get_category_by_path(&self, path: &str, vocab: &VocabDatabase) -> Option<VocabCategory> {
+    pub fn get_category_by_path(&self, path: &str, user: &UserDictionary) -> Option<VocabCategory> {
         // Split path
         let parts: Vec<&str> = path.split("/").collect::<Vec<&str>>();
         if parts.len() < 2 {
             return None;
         }
-        let remaining_path = parts[1..].join("/").to_string();
 
         // Get index
-        let cat_index = match parts[0] {
-            "nouns" => self.nouns.index_by_path(&remaining_path)?,
-            "verbs" => self.verbs.index_by_path(&remaining_path)?,
-            "adverbs" => self.adverbs.index_by_path(&remaining_path)?,
-            "adjectives" => self.adjectives.index_by_path(&remaining_path)?,
-            "ner" => self.ner.index_by_path(&remaining_path)?,
-            _ => return None,
-        };
-        let index = cat_index.index;
+        let index = self.path2id(path)?;
 
         // Get category
         let mut cat: VocabCategory = self.nodes.get(&index)?.clone();
         cat.pos = parts[0].to_string();
-        cat.words = vocab
-            .words
-            .id2token
-            .iter()
-            .filter(|(_, token)| token.categories.contains(&index))
-            .map(|(id, _)| *id)
-            .collect();
+        cat.words = self.get_words(index, user);
 
         Some(cat)
     }
+
+    /// Retrieves the IDs of all words assigned to a category, including user dictionary words, sorted by ID.
+    ///
+    /// NER labels share the category ID space under the `ner/` root, so the words of a NER label (eg. ner/person) are the named
+    /// entities tagged with it.
+    pub fn get_words(&self, category_id: i16, user: &UserDictionary) -> Vec<i32> {
+        let mut words = self.word_index.get(&category_id).cloned().unwrap_or_default();
+        words.extend_from_slice(user.get_category_words(category_id));
+        words.sort();
+        words
+    }
+
+    /// Lists the IDs of all words within a category and its descendants, sorted by ID and paginated.
+    ///
+    /// Returns the requested page of word IDs along with the total number of words within the subtree, or None if the path does not exist.
+    pub fn get_subtree_words(
+        &self,
+        path: &str,
+        offset: usize,
+        limit: usize,
+        user: &UserDictionary,
+    ) -> Option<(Vec<i32>, usize)> {
+        let range = self.path2range(path)?;
+
+        // Gather words
+        let mut words: Vec<i32> = Vec::new();
+        for category_id in range {
+            words.extend(self.get_words(category_id, user));
+        }
+        words.sort();
+        words.dedup();
+
+        let total = words.len();
+        let page = words.into_iter().skip(offset).take(limit).collect();
+        Some((page, total))
+    }
+
+    /// Returns the root index for a top-level category name (nouns, verbs, adverbs, adjectives or ner).
+    pub fn root(&self, name: &str) -> Option<&VocabCategoryIndex> {
+        match name {
+            "nouns" => Some(&self.nouns),
+            "verbs" => Some(&self.verbs),
+            "adverbs" => Some(&self.adverbs),
+            "adjectives" => Some(&self.adjectives),
+            "ner" => Some(&self.ner),
+            _ => None,
+        }
+    }
+
+    /// Converts a full category path (eg. verbs/action/search) into its category ID, if it exists.
+    pub fn path2id(&self, path: &str) -> Option<i16> {
+        let (root, remaining_path) = path.split_once("/")?;
+        self.root(root)?.by_path(remaining_path)
+    }
+
+    /// Returns the range of category IDs for a full category path, including its descendants, if the path exists.
+    pub fn path2range(&self, path: &str) -> Option<Range<i16>> {
+        let (root, remaining_path) = path.split_once("/")?;
+        self.root(root)?.path2range(remaining_path)
+    }
 }
 
 impl VocabCategoryIndex {
@@ -159,10 +206,12 @@ impl VocabCategoryDatabase {
         }
     }
 
-    /// Returns the number of children for a given category ID.
+    /// Returns the number of children for a given category ID, or 0 if it does not exist.
     pub fn get_children_count(&self, category_id: &i16) -> usize {
-        let node = self.nodes.get(&category_id.clone()).unwrap();
-        node.children.len()
+        match self.nodes.get(category_id) {
+            Some(node) => node.children.len(),
+            None => 0,
+        }
     }
 
     /// Retrieves the fully qualified names of a category's parent categories.
@@ -178,6 +227,91 @@ impl VocabCategoryDatabase {
 
         names
     }
+
+    /// Converts a category ID into its full category path (eg. nouns/transportation/automobile), if it exists.
+    pub fn id2path(&self, category_id: i16) -> Option<String> {
+        let root = self.root_name(category_id)?;
+
+        let mut names: Vec<String> = vec![root.to_string()];
+        for id in self.lineage(category_id)? {
+            names.push(self.nodes.get(&id)?.name.to_string());
+        }
+        Some(names.join("/"))
+    }
+
+    /// Returns the name of the top-level root (nouns, verbs, adverbs, adjectives or ner) a category falls under.
+    pub fn root_name(&self, category_id: i16) -> Option<&'static str> {
+        let top_id = *self.lineage(category_id)?.first()?;
+        ["nouns", "verbs", "adverbs", "adjectives", "ner"].into_iter().find(|name| {
+            self.root(name).is_some_and(|root| root.children.values().any(|c| c.index == top_id))
+        })
+    }
+
+    /// Returns the ID of the parent category, or None if the category is top-level or does not exist.
+    pub fn get_parent(&self, category_id: i16) -> Option<i16> {
+        let lineage = self.lineage(category_id)?;
+        lineage.iter().rev().nth(1).copied()
+    }
+
+    /// Returns the full paths of all ancestors of a category, ordered from the top-level category down to the parent.
+    pub fn get_ancestors(&self, category_id: i16) -> Vec<String> {
+        let mut lineage = self.lineage(category_id).unwrap_or_default();
+        lineage.pop();
+        lineage.into_iter().filter_map(|id| self.id2path(id)).collect()
+    }
+
+    /// Returns the IDs of the direct children of a category.
+    pub fn get_children(&self, category_id: i16) -> Vec<i16> {
+        match self.nodes.get(&category_id) {
+            Some(node) => node.children.values().copied().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the IDs of all descendants of a category, in depth-first order.
+    pub fn get_descendants(&self, category_id: i16) -> Vec<i16> {
+        let mut descendants: Vec<i16> = Vec::new();
+        for child_id in self.get_children(category_id) {
+            descendants.push(child_id);
+            descendants.extend(self.get_descendants(child_id));
+        }
+        descendants
+    }
+
+    /// Returns the IDs of all categories sharing the same parent, excluding the category itself.
+    pub fn get_siblings(&self, category_id: i16) -> Vec<i16> {
+        let siblings: Vec<i16> = match self.get_parent(category_id) {
+            Some(parent_id) => self.get_children(parent_id),
+            None => match self.root_name(category_id).and_then(|name| self.root(name)) {
+                Some(root) => root.children.values().map(|child| child.index).collect(),
+                None => Vec::new(),
+            },
+        };
+
+        siblings.into_iter().filter(|id| *id != category_id).collect()
+    }
+
+    /// Returns the ID of the lowest common ancestor of two categories, which may be either category itself, or None if they share no ancestor.
+    pub fn lowest_common_ancestor(&self, category1: i16, category2: i16) -> Option<i16> {
+        let lineage1 = self.lineage(category1)?;
+        let lineage2 = self.lineage(category2)?;
+
+        lineage1.iter().zip(lineage2.iter()).take_while(|(a, b)| a == b).map(|(a, _)| *a).last()
+    }
+
+    /// Returns the depth of a category, with top-level categories having a depth of 1, or 0 if it does not exist.
+    pub fn get_depth(&self, category_id: i16) -> usize {
+        self.lineage(category_id).map(|lineage| lineage.len()).unwrap_or(0)
+    }
+
+    /// Returns the IDs from the top-level category down to and including the category itself.
+    fn lineage(&self, category_id: i16) -> Option<Vec<i16>> {
+        let node = self.nodes.get(&category_id)?;
+        let mut lineage: Vec<i16> =
+            node.fqn.iter().filter(|id| **id != category_id).copied().collect();
+        lineage.push(category_id);
+        Some(lineage)
+    }
 }
 
 impl VocabCategory {}
@@ -204,3 +338,103 @@ impl Default for VocabCategoryIndex {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::pos_tagger::POSTag;
+    use crate::vocab::fixtures::{self, TempDir};
+    use crate::vocab::{UserWord, VocabDatabase};
+
+    /// Returns the sorted token IDs of the given words and POS tags.
+    fn ids(vocab: &VocabDatabase, words: &[(&str, POSTag)]) -> Vec<i32> {
+        let mut res: Vec<i32> =
+            words.iter().map(|(word, tag)| vocab.words.wordlist[*word][tag]).collect();
+        res.sort();
+        res
+    }
+
+    #[test]
+    fn test_get_words() {
+        let mut vocab = fixtures::vocab();
+        let automobile = vocab.categories.path2id("nouns/transportation/automobile").unwrap();
+        let expected = ids(
+            &vocab,
+            &[
+                ("car", POSTag::NN),
+                ("cars", POSTag::NNS),
+                ("automobile", POSTag::NN),
+                ("truck", POSTag::NN),
+            ],
+        );
+        assert_eq!(
+            vocab.categories.get_words(automobile, &vocab.user),
+            expected
+        );
+
+        // User words added after the first lookup are included
+        let mut entry = UserWord::new("minivan", POSTag::NN);
+        entry.categories = vec!["nouns/transportation/automobile".to_string()];
+        let index = vocab.add_user_word(&entry).unwrap();
+        let words = vocab.categories.get_words(automobile, &vocab.user);
+        assert_eq!(words, [expected.clone(), vec![index]].concat());
+
+        let cat = vocab
+            .categories
+            .get_category_by_path("nouns/transportation/automobile", &vocab.user)
+            .unwrap();
+        assert_eq!(cat.pos, "nouns");
+        assert_eq!(cat.words, words);
+
+        // Subtrees include the words of all descendants, paginated by ID
+        let (page, total) =
+            vocab.categories.get_subtree_words("nouns/transportation", 0, 2, &vocab.user).unwrap();
+        assert_eq!(total, 6);
+        assert_eq!(page, expected[..2].to_vec());
+        let (page, _) =
+            vocab.categories.get_subtree_words("nouns/transportation", 5, 2, &vocab.user).unwrap();
+        assert_eq!(page, vec![index]);
+        assert!(vocab.categories.get_subtree_words("nouns/food", 0, 2, &vocab.user).is_none());
+    }
+
+    #[test]
+    fn test_ner_words() {
+        let mut vocab = fixtures::vocab();
+        let person = vocab.categories.path2id("ner/person").unwrap();
+
+        // Named entities are listed by their NER label, which is kept apart from their categories
+        let matt = ids(&vocab, &[("matt", POSTag::NNP)]);
+        assert_eq!(vocab.categories.get_words(person, &vocab.user), matt);
+        assert!(vocab.words.id2token[&matt[0]].categories.is_empty());
+
+        let mut entry = UserWord::new("alice", POSTag::NNP);
+        entry.ner = vec!["person".to_string()];
+        let index = vocab.add_user_word(&entry).unwrap();
+        assert_eq!(
+            vocab.categories.get_words(person, &vocab.user),
+            vec![matt[0], index]
+        );
+    }
+
+    #[test]
+    fn test_loaded_words() {
+        let dir = TempDir::new("category-loaded");
+        let mut vocab = fixtures::vocab();
+        vocab.save(&dir.file("en.dat")).unwrap();
+        vocab.save_mapped(&dir.file("en.mdat")).unwrap();
+
+        // Indexed upon load, both read and memory-mapped
+        let loaded = VocabDatabase::load(dir.path(), "en").unwrap();
+        let mapped = VocabDatabase::load_mapped(dir.path(), "en").unwrap();
+        for category_id in 1..=vocab.categories.counter {
+            let words = vocab.categories.get_words(category_id, &vocab.user);
+            assert_eq!(
+                loaded.categories.get_words(category_id, &loaded.user),
+                words
+            );
+            assert_eq!(
+                mapped.categories.get_words(category_id, &mapped.user),
+                words
+            );
+        }
+    }
+}
@@ -4,20 +4,36 @@
 // License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
 // Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
 
+use super::search::SearchEntry;
 use super::{
-    FutureVerbPhrases, PhraseIntents, SpellChecker, VocabCache, VocabCategoryDatabase, VocabMWE,
+    FutureVerbPhrases, MWENode, MappedVocab, PhraseIntents, SpellChecker, UserDictionary, UserWord,
+    VocabCache, VocabCategoryDatabase, VocabMWE,
 };
-use crate::error::Error;
+use crate::error::{Error, LoadError};
 use crate::pos_tagger::{POSTag, POSTagger};
 use crate::tokenizer::Token;
 use crate::vocab::mwe::Capitalization;
 use bincode;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::fs;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
 use std::path::Path;
-use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The major version of the data store format, with data stores of any other major version refused upon load.
+const MAJOR_VERSION: i8 = 1;
+
+/// The magic number at the start of every zstd frame, used to detect compressed data stores.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// A word within the wordlist, along with its POS-to-ID mapping, either borrowed or read from a memory-mapped store.
+pub type WordEntry<'a> = (Cow<'a, str>, Cow<'a, IndexMap<POSTag, i32>>);
 
 /// A comprehensive vocabulary database for natural language processing, containing metadata, preprocessing data, words, categories, and a cache.
 #[derive(Serialize, Deserialize)]
@@ -27,11 +43,13 @@ pub struct VocabDatabase {
     pub words: VocabWordDatabase,
     pub categories: VocabCategoryDatabase,
     #[serde(skip_serializing, skip_deserializing)]
-    pub cache: Mutex<VocabCache>,
+    pub cache: VocabCache,
+    #[serde(skip_serializing, skip_deserializing)]
+    pub user: UserDictionary,
 }
 
 /// Metadata for the vocabulary database, including version, language, author, and integrity details.
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct VocabDatabaseMeta {
     version: (i8, i8, i8),
     language: String,
@@ -42,6 +60,17 @@ pub struct VocabDatabaseMeta {
     comment: String,
 }
 
+/// Integrity checks applied when loading a vocabulary data store, with the payload hash and major version always verified.
+#[derive(Clone, Default, Debug)]
+pub struct VocabLoadOptions {
+    /// Verify the data store is signed by this ed25519 public key before it is returned.
+    pub public_key: Option<[u8; 32]>,
+    /// Allow data stores without a payload hash, such as those created before sealing, which are otherwise refused.
+    pub allow_unsealed: bool,
+    /// Hash the entire payload of memory-mapped data stores, which reads every page of the file.  Always done when a public key is given.
+    pub verify_mapped: bool,
+}
+
 /// Preprocessing data for the vocabulary, including hashes, typos, spellchecker, verb prefixes, and other linguistic resources.
 #[derive(Serialize, Deserialize, Clone)]
 pub struct VocabPreProcessDatabase {
@@ -65,49 +94,313 @@ pub struct VocabWordDatabase {
     pub phrase_intents: PhraseIntents,
     pub id2token: HashMap<i32, Token>,
     pub plural: HashMap<i32, i32>,
+    #[serde(skip_serializing, skip_deserializing)]
+    pub mapped: Option<MappedVocab>,
+    #[serde(skip_serializing, skip_deserializing)]
+    pub(crate) stem_index: OnceLock<HashMap<i32, Vec<i32>>>,
+    #[serde(skip_serializing, skip_deserializing)]
+    pub(crate) plural_index: OnceLock<HashMap<i32, i32>>,
+    #[serde(skip_serializing, skip_deserializing)]
+    pub(crate) search_index: OnceLock<Vec<SearchEntry>>,
+    #[serde(skip_serializing, skip_deserializing)]
+    pub(crate) mwe_index: OnceLock<HashMap<String, Vec<i32>>>,
 }
 
 impl VocabDatabase {
-    /// Saves the vocabulary database to a file using bincode serialization.
+    /// Saves the vocabulary database to a file using bincode serialization, sealed with the SHA256 hash of its payload.
     pub fn save(&mut self, filename: &str) -> Result<(), Error> {
-        let encoded: Vec<u8> = match bincode::serialize(&self) {
-            Ok(r) => r,
-            Err(e) => {
-                return Err(Error::Save(format!(
-                    "Unable to serialize vocabulary data store, {}",
-                    e
-                )));
-            }
-        };
-        fs::write(filename, &encoded)?;
-        Ok(())
+        self.write(filename, None, None)
+    }
+
+    /// Saves the vocabulary database to a file, sealed with the SHA256 hash of its payload and signed with the given ed25519 secret key.
+    pub fn save_signed(&mut self, filename: &str, secret_key: &[u8; 32]) -> Result<(), Error> {
+        self.write(filename, Some(secret_key), None)
+    }
+
+    /// Saves the vocabulary database to a zstd compressed file (eg. en.dat.zst), sealed with the SHA256 hash of its uncompressed payload.
+    ///
+    /// Compression levels range from 1 to 22, with 19 giving a good balance of size and time for distribution.
+    pub fn save_compressed(&mut self, filename: &str, level: i32) -> Result<(), Error> {
+        self.write(filename, None, Some(level))
+    }
+
+    /// Saves the vocabulary database to a zstd compressed file, signed with the given ed25519 secret key.
+    pub fn save_compressed_signed(
+        &mut self,
+        filename: &str,
+        level: i32,
+        secret_key: &[u8; 32],
+    ) -> Result<(), Error> {
+        self.write(filename, Some(secret_key), Some(level))
     }
 
     /// Loads a vocabulary database from a file in the specified directory, initializing the cache.
+    ///
+    /// Loads `{language}.dat`, or `{language}.dat.zst` if it does not exist, with zstd compression detected by its magic
+    /// header and decompressed as the file is read.  The format and major versions are verified before the database is deserialized,
+    /// and the hash of each section as it is read.  Data stores written before the versioned header are migrated upon load, and
+    /// written with the header when next saved.  Unsealed data stores are refused, see `load_with()`.
     pub fn load(datadir: &str, language: &str) -> Result<VocabDatabase, Error> {
+        Self::load_with(datadir, language, &VocabLoadOptions::default())
+    }
+
+    /// Loads a vocabulary database as per `load()`, applying the given integrity checks before it is returned.
+    pub fn load_with(
+        datadir: &str,
+        language: &str,
+        options: &VocabLoadOptions,
+    ) -> Result<VocabDatabase, Error> {
         let filename = format!("{}/{}.dat", datadir, language);
+        let Some(filename) = [filename.clone(), format!("{}.zst", filename)]
+            .into_iter()
+            .find(|f| Path::new(f).exists())
+        else {
+            return Err(Error::Load(LoadError::NotFound(filename)));
+        };
+        let mut reader = BufReader::new(File::open(&filename)?);
+
+        let mut vocab = if reader.fill_buf()?.starts_with(&ZSTD_MAGIC) {
+            let decoder = zstd::stream::read::Decoder::with_buffer(reader)?;
+            Self::read_from(decoder, options.allow_unsealed)?
+        } else {
+            Self::read_from(reader, options.allow_unsealed)?
+        };
+
+        if let Some(public_key) = &options.public_key {
+            vocab.meta.verify_signature(public_key)?;
+        }
+        vocab.index_category_words();
+        vocab.cache = VocabCache::load(datadir)?;
+        Ok(vocab)
+    }
+
+    /// Saves the vocabulary database to a file in the memory-mappable layout, see `load_mapped()`.
+    pub fn save_mapped(&mut self, filename: &str) -> Result<(), Error> {
+        MappedVocab::save(self, filename, None)
+    }
+
+    /// Saves the vocabulary database in the memory-mappable layout, signed with the given ed25519 secret key.
+    pub fn save_mapped_signed(
+        &mut self,
+        filename: &str,
+        secret_key: &[u8; 32],
+    ) -> Result<(), Error> {
+        MappedVocab::save(self, filename, Some(secret_key))
+    }
+
+    /// Loads a memory-mapped vocabulary database (eg. en.mdat) from the specified directory, initializing the cache.
+    ///
+    /// The wordlist, tokens and MWE trie are queried in place from the mapped file instead of
+    /// being deserialized, allowing the OS page cache to be shared between processes.  Only the major version is
+    /// verified, as hashing the payload would read every page of the file, see `load_mapped_verified()`.
+    pub fn load_mapped(datadir: &str, language: &str) -> Result<VocabDatabase, Error> {
+        Self::load_mapped_with(datadir, language, &VocabLoadOptions::default())
+    }
+
+    /// Loads a memory-mapped vocabulary database as per `load_mapped()`, additionally verifying the SHA256 hash of the entire file.
+    pub fn load_mapped_verified(datadir: &str, language: &str) -> Result<VocabDatabase, Error> {
+        let options = VocabLoadOptions {
+            verify_mapped: true,
+            ..Default::default()
+        };
+        Self::load_mapped_with(datadir, language, &options)
+    }
+
+    /// Loads a memory-mapped vocabulary database as per `load_mapped()`, applying the given integrity checks before it is returned.
+    pub fn load_mapped_with(
+        datadir: &str,
+        language: &str,
+        options: &VocabLoadOptions,
+    ) -> Result<VocabDatabase, Error> {
+        let filename = format!("{}/{}.mdat", datadir, language);
         if !Path::new(&filename).exists() {
-            return Err(Error::Load(format!(
-                "No vocabulary file exists at, {}",
-                filename
-            )));
+            return Err(Error::Load(LoadError::NotFound(filename)));
         }
-        let contents = fs::read(&filename)?;
+        let mapped = MappedVocab::open(&filename)?;
 
-        let mut vocab: VocabDatabase = match bincode::deserialize(&contents[..]) {
+        // Verify integrity
+        let meta: VocabDatabaseMeta = match bincode::deserialize(mapped.base()) {
             Ok(r) => r,
             Err(e) => {
-                return Err(Error::Load(format!(
-                    "Unable to load the vocabulary file.  Please ensure correct file is in place, and re-download from secure client area if necessary.  Contact customer support if the problem persists.  Error: {}",
+                return Err(Error::Load(LoadError::Corrupt(format!(
+                    "Unable to read the memory-mapped vocabulary file metadata, {}",
                     e
-                )));
+                ))));
+            }
+        };
+        meta.verify_version()?;
+        meta.verify_sealed(options.allow_unsealed)?;
+        if options.verify_mapped || options.public_key.is_some() {
+            let payload = mapped.payload(meta.encoded_len()?);
+            meta.verify_hash(&VocabDatabaseMeta::hash(&payload))?;
+        }
+        if let Some(public_key) = &options.public_key {
+            meta.verify_signature(public_key)?;
+        }
+
+        let mut vocab: VocabDatabase = match bincode::deserialize(mapped.base()) {
+            Ok(r) => r,
+            Err(e) => {
+                return Err(Error::Load(LoadError::Corrupt(format!(
+                    "Unable to load the memory-mapped vocabulary file, {}.  Error: {}",
+                    filename, e
+                ))));
             }
         };
 
-        vocab.cache = Mutex::new(VocabCache::load(datadir)?);
+        vocab.words.mapped = Some(mapped);
+        vocab.index_category_words();
+        vocab.cache = VocabCache::load(datadir)?;
         Ok(vocab)
     }
 
+    /// Loads a user dictionary file (TOML if it has a .toml extension, otherwise tab delimited text) into the user overlay, returning the number of entries added.
+    pub fn load_user_dictionary(&mut self, filename: &str) -> Result<usize, Error> {
+        if !Path::new(filename).exists() {
+            return Err(Error::Load(LoadError::NotFound(filename.to_string())));
+        }
+        let contents = fs::read_to_string(filename)?;
+
+        let entries = UserDictionary::parse(&contents, filename.to_lowercase().ends_with(".toml"))?;
+        for entry in entries.iter() {
+            self.add_user_word(entry)?;
+        }
+
+        Ok(entries.len())
+    }
+
+    /// Adds a word or MWE to the user overlay, resolving its category paths, and returns its token ID.
+    pub fn add_user_word(&mut self, entry: &UserWord) -> Result<i32, Error> {
+        let (mut categories, mut ner) = (Vec::new(), Vec::new());
+
+        // Resolve categories, with NER paths placed within the NER labels
+        for path in entry.categories.iter() {
+            let category_id = match self.categories.path2id(path) {
+                Some(r) => r,
+                None => return Err(Error::Generic(format!("Unknown category path, {}", path))),
+            };
+            if path.to_lowercase().starts_with("ner/") {
+                ner.push(category_id);
+            } else {
+                categories.push(category_id);
+            }
+        }
+
+        // Resolve NER labels, with or without the ner/ prefix
+        for label in entry.ner.iter() {
+            let path = if label.to_lowercase().starts_with("ner/") {
+                label.to_string()
+            } else {
+                format!("ner/{}", label)
+            };
+            match self.categories.path2id(&path) {
+                Some(category_id) => ner.push(category_id),
+                None => return Err(Error::Generic(format!("Unknown NER label, {}", label))),
+            };
+        }
+
+        Ok(self.user.add(entry, categories, ner))
+    }
+
+    /// Retrieves the POS-to-ID mapping of a lowercase word, with user dictionary entries taking precedence over the wordlist.
+    pub fn get_pos_map(&self, word: &str) -> Option<IndexMap<POSTag, i32>> {
+        let pos_map = match &self.words.mapped {
+            Some(mapped) => mapped.get_pos_map(word),
+            None => self.words.wordlist.get(word).cloned(),
+        };
+
+        // Check user dictionary
+        let Some(user_map) = self.user.wordlist.get(word) else {
+            return pos_map;
+        };
+        let mut res = user_map.clone();
+        for (tag, token_id) in pos_map.unwrap_or_default() {
+            res.entry(tag).or_insert(token_id);
+        }
+
+        Some(res)
+    }
+
+    /// Retrieves the token of the given ID with its index set, or None if it does not exist.
+    pub fn get_token(&self, token_id: i32) -> Option<Token> {
+        let mut token = if self.user.contains_id(token_id) {
+            self.user.id2token.get(&token_id)?.clone()
+        } else {
+            match &self.words.mapped {
+                Some(mapped) => mapped.get_token(token_id)?,
+                None => self.words.id2token.get(&token_id)?.clone(),
+            }
+        };
+        token.index = token_id;
+        Some(token)
+    }
+
+    /// Returns the root node of the MWE trie.
+    pub fn mwe_root(&self) -> MWENode<'_> {
+        match &self.words.mapped {
+            Some(mapped) => MWENode::Mapped(mapped.mwe_root()),
+            None => MWENode::Owned(&self.words.mwe),
+        }
+    }
+
+    /// Iterates over all words within the wordlist, along with their POS-to-ID mappings.
+    pub fn iter_words(&self) -> Box<dyn Iterator<Item = WordEntry<'_>> + '_> {
+        match &self.words.mapped {
+            Some(mapped) => Box::new(mapped.words().map(|(w, p)| (Cow::Owned(w), Cow::Owned(p)))),
+            None => Box::new(
+                self.words
+                    .wordlist
+                    .iter()
+                    .map(|(w, p)| (Cow::Borrowed(w.as_str()), Cow::Borrowed(p))),
+            ),
+        }
+    }
+
+    /// Iterates over all tokens within the vocabulary, along with their token IDs.
+    pub fn iter_tokens(&self) -> Box<dyn Iterator<Item = (i32, Cow<'_, Token>)> + '_> {
+        match &self.words.mapped {
+            Some(mapped) => Box::new(mapped.tokens().map(|(id, t)| (id, Cow::Owned(t)))),
+            None => Box::new(self.words.id2token.iter().map(|(id, t)| (*id, Cow::Borrowed(t)))),
+        }
+    }
+
+    /// Builds the inverted index of category and NER label IDs to the IDs of words assigned to them, once loaded or built.
+    pub(crate) fn index_category_words(&mut self) {
+        let mut index: HashMap<i16, Vec<i32>> = HashMap::new();
+        for (token_id, token) in self.iter_tokens() {
+            for category_id in token.categories.iter().chain(token.ner.iter()) {
+                index.entry(*category_id).or_default().push(token_id);
+            }
+        }
+        for words in index.values_mut() {
+            words.sort();
+        }
+        self.categories.word_index = index;
+    }
+
+    /// Verifies the signature of the data store against the given ed25519 public key.
+    ///
+    /// The payload hash is verified against the data store contents upon load, so a valid signature confirms the entire payload.
+    pub fn verify_signature(&self, public_key: &[u8; 32]) -> Result<(), Error> {
+        self.meta.verify_signature(public_key)
+    }
+
+    /// Encodes the data store with its header and sealed metadata, and writes it to the given filename, compressed if a level is given.
+    fn write(
+        &mut self,
+        filename: &str,
+        secret_key: Option<&[u8; 32]>,
+        compression_level: Option<i32>,
+    ) -> Result<(), Error> {
+        let encoded = self.encode(secret_key)?;
+
+        match compression_level {
+            Some(level) => zstd::stream::copy_encode(&encoded[..], File::create(filename)?, level)?,
+            None => fs::write(filename, &encoded)?,
+        };
+        Ok(())
+    }
+
     /// Looks up a word by string, returning a Token based on its vocabulary entry.
     pub fn from_str(&self, word: &str) -> Token {
         let (_, lookup) = match self.lookup_word(word) {
@@ -115,9 +408,8 @@ impl VocabDatabase {
             None => return Token::default(),
         };
 
-        // get token
-        let token_id = lookup.values().next().unwrap();
-        self.words.id2token.get(&token_id.clone()).unwrap().clone()
+        // get token, with a dangling ID treated as unknown
+        lookup.values().next().and_then(|token_id| self.get_token(*token_id)).unwrap_or_default()
     }
 
     /// Converts a word to its corresponding token ID.
@@ -134,13 +426,13 @@ impl VocabDatabase {
         }
 
         // Straight lookup
-        if let Some(pos_map) = self.words.wordlist.get(&word.to_lowercase().to_string()) {
-            return Some((word.to_string(), pos_map.clone()));
+        if let Some(pos_map) = self.get_pos_map(&word.to_lowercase().to_string()) {
+            return Some((word.to_string(), pos_map));
         }
 
         // Lowercase lookup
-        if let Some(index) = self.words.wordlist.get(&word.to_lowercase()) {
-            return Some((word.to_string(), index.clone()));
+        if let Some(index) = self.get_pos_map(&word.to_lowercase()) {
+            return Some((word.to_string(), index));
         }
 
         None
@@ -152,10 +444,162 @@ impl VocabDatabase {
     }
 }
 
+impl VocabDatabaseMeta {
+    /// Creates new metadata for the given language, author and comment, stamped with the current creation time.
+    pub fn new(language: &str, author: &str, comment: &str) -> Self {
+        let creation_time = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(r) => r.as_secs().to_string(),
+            Err(_) => String::new(),
+        };
+
+        Self {
+            language: language.to_string(),
+            author: author.to_string(),
+            creation_time,
+            comment: comment.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the version of the data store as (major, minor, patch).
+    pub fn version(&self) -> (i8, i8, i8) {
+        self.version
+    }
+
+    /// Returns the language code of the data store.
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Returns the author of the data store.
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    /// Returns the creation time of the data store, in seconds since the UNIX epoch.
+    pub fn creation_time(&self) -> &str {
+        &self.creation_time
+    }
+
+    /// Returns the hex encoded SHA256 hash of the data store payload, or blank if unsealed.
+    pub fn sha256_hash(&self) -> &str {
+        &self.sha256_hash
+    }
+
+    /// Returns the hex encoded ed25519 signature of the data store, or blank if unsigned.
+    pub fn signature(&self) -> &str {
+        &self.signature
+    }
+
+    /// Returns the comment of the data store.
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    /// Seals the metadata with the SHA256 hash of the payload, signing it if a secret key is given.
+    pub(crate) fn seal(&mut self, payload: &[&[u8]], secret_key: Option<&[u8; 32]>) {
+        self.sha256_hash = Self::hash(payload);
+        self.signature = match secret_key {
+            Some(key) => {
+                let signature = SigningKey::from_bytes(key).sign(self.signing_message().as_bytes());
+                hex::encode(signature.to_bytes())
+            }
+            None => String::new(),
+        };
+    }
+
+    /// Verifies the major version of the data store.
+    pub(crate) fn verify_version(&self) -> Result<(), Error> {
+        if self.version.0 != MAJOR_VERSION {
+            return Err(Error::Load(LoadError::VersionMismatch {
+                expected: MAJOR_VERSION,
+                found: self.version,
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the data store is sealed with a payload hash, unless unsealed data stores are allowed.
+    pub(crate) fn verify_sealed(&self, allow_unsealed: bool) -> Result<(), Error> {
+        if self.sha256_hash.is_empty() && !allow_unsealed {
+            return Err(Error::Load(LoadError::MissingHash));
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the hex encoded SHA256 hash of the payload against the stored hash, passing unsealed data stores once allowed by `verify_sealed()`.
+    pub(crate) fn verify_hash(&self, hash: &str) -> Result<(), Error> {
+        if !self.sha256_hash.is_empty() && hash != self.sha256_hash {
+            return Err(Error::Load(LoadError::HashMismatch {
+                expected: self.sha256_hash.clone(),
+                found: hash.to_string(),
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the signature against the given ed25519 public key.
+    pub fn verify_signature(&self, public_key: &[u8; 32]) -> Result<(), Error> {
+        if self.sha256_hash.is_empty() || self.signature.is_empty() {
+            return Err(Error::Load(LoadError::MissingSignature));
+        }
+
+        let key = match VerifyingKey::from_bytes(public_key) {
+            Ok(r) => r,
+            Err(e) => return Err(Error::Load(LoadError::InvalidKey(e.to_string()))),
+        };
+
+        // Decode signature
+        let signature = match hex::decode(&self.signature) {
+            Ok(bytes) => match Signature::from_slice(&bytes) {
+                Ok(r) => r,
+                Err(_) => return Err(Error::Load(LoadError::InvalidSignature)),
+            },
+            Err(_) => return Err(Error::Load(LoadError::InvalidSignature)),
+        };
+
+        match key.verify(self.signing_message().as_bytes(), &signature) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::Load(LoadError::InvalidSignature)),
+        }
+    }
+
+    /// Returns the length of the metadata once bincode encoded, being the offset of the payload.
+    pub(crate) fn encoded_len(&self) -> Result<usize, Error> {
+        match bincode::serialized_size(self) {
+            Ok(r) => Ok(r as usize),
+            Err(e) => Err(Error::Load(LoadError::Corrupt(format!(
+                "Unable to size vocabulary metadata, {}",
+                e
+            )))),
+        }
+    }
+
+    /// Returns the message that is signed, binding the payload hash to the language and version.
+    fn signing_message(&self) -> String {
+        format!(
+            "{}:{}.{}.{}:{}",
+            self.language, self.version.0, self.version.1, self.version.2, self.sha256_hash
+        )
+    }
+
+    /// Returns the hex encoded SHA256 hash of the payload slices.
+    fn hash(payload: &[&[u8]]) -> String {
+        let mut hasher = Sha256::new();
+        for chunk in payload.iter() {
+            hasher.update(chunk);
+        }
+        hex::encode(hasher.finalize())
+    }
+}
+
 impl Default for VocabDatabaseMeta {
     fn default() -> VocabDatabaseMeta {
         VocabDatabaseMeta {
-            version: (1, 0, 0),
+            version: (MAJOR_VERSION, 0, 0),
             language: "en".to_string(),
             author: "Aquila Labs".to_string(),
             creation_time: String::new(),
@@ -165,3 +609,154 @@ impl Default for VocabDatabaseMeta {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::fixtures::{self, TempDir};
+
+    /// Returns the ed25519 secret and public keys derived from the seed byte.
+    fn keypair(seed: u8) -> ([u8; 32], [u8; 32]) {
+        let secret_key = [seed; 32];
+        let public_key = SigningKey::from_bytes(&secret_key).verifying_key().to_bytes();
+        (secret_key, public_key)
+    }
+
+    /// Saves the vocabulary within the directory as both en.dat and en.mdat, signed if a secret key is given.
+    fn save_both(dir: &TempDir, vocab: &mut VocabDatabase, secret_key: Option<&[u8; 32]>) {
+        match secret_key {
+            Some(key) => {
+                vocab.save_signed(&dir.file("en.dat"), key).unwrap();
+                vocab.save_mapped_signed(&dir.file("en.mdat"), key).unwrap();
+            }
+            None => {
+                vocab.save(&dir.file("en.dat")).unwrap();
+                vocab.save_mapped(&dir.file("en.mdat")).unwrap();
+            }
+        }
+    }
+
+    /// Loads the data store within the directory, both read and memory-mapped, with the given options.
+    fn load_both(dir: &TempDir, options: &VocabLoadOptions) -> [Result<VocabDatabase, Error>; 2] {
+        [
+            VocabDatabase::load_with(dir.path(), "en", options),
+            VocabDatabase::load_mapped_with(dir.path(), "en", options),
+        ]
+    }
+
+    #[test]
+    fn test_unsealed() {
+        let dir = TempDir::new("database-unsealed");
+        let vocab = fixtures::vocab();
+        assert!(vocab.meta.sha256_hash().is_empty());
+
+        // Legacy layout of metadata followed by the payload, without a hash
+        let mut data = bincode::serialize(&vocab.meta).unwrap();
+        data.extend(
+            bincode::serialize(&(&vocab.preprocess, &vocab.words, &vocab.categories)).unwrap(),
+        );
+        fs::write(dir.file("en.dat"), &data).unwrap();
+
+        assert!(matches!(
+            VocabDatabase::load(dir.path(), "en"),
+            Err(Error::Load(LoadError::MissingHash))
+        ));
+
+        let options = VocabLoadOptions {
+            allow_unsealed: true,
+            ..Default::default()
+        };
+        let loaded = VocabDatabase::load_with(dir.path(), "en", &options).unwrap();
+        assert_eq!(loaded.words.wordlist.len(), vocab.words.wordlist.len());
+
+        // Unsealed data stores are never signed
+        let (_, public_key) = keypair(7);
+        let options = VocabLoadOptions {
+            allow_unsealed: true,
+            public_key: Some(public_key),
+            ..Default::default()
+        };
+        assert!(matches!(
+            VocabDatabase::load_with(dir.path(), "en", &options),
+            Err(Error::Load(LoadError::MissingSignature))
+        ));
+    }
+
+    #[test]
+    fn test_hash_mismatch() {
+        let dir = TempDir::new("database-hash");
+        save_both(&dir, &mut fixtures::vocab(), None);
+
+        for filename in ["en.dat", "en.mdat"] {
+            let mut data = fs::read(dir.file(filename)).unwrap();
+            let last = data.len() - 1;
+            data[last] ^= 0x01;
+            fs::write(dir.file(filename), &data).unwrap();
+        }
+
+        let options = VocabLoadOptions {
+            verify_mapped: true,
+            ..Default::default()
+        };
+        for res in load_both(&dir, &options) {
+            assert!(matches!(
+                res,
+                Err(Error::Load(LoadError::HashMismatch { .. }))
+                    | Err(Error::Load(LoadError::SectionHashMismatch { .. }))
+            ));
+        }
+
+        // Mapped data stores are not hashed unless requested
+        assert!(VocabDatabase::load_mapped(dir.path(), "en").is_ok());
+    }
+
+    #[test]
+    fn test_version_mismatch() {
+        let dir = TempDir::new("database-version");
+        let mut vocab = fixtures::vocab();
+        vocab.meta.version = (MAJOR_VERSION + 1, 0, 0);
+        save_both(&dir, &mut vocab, None);
+
+        for res in load_both(&dir, &VocabLoadOptions::default()) {
+            assert!(matches!(
+                res,
+                Err(Error::Load(LoadError::VersionMismatch { expected, found }))
+                    if expected == MAJOR_VERSION && found == (MAJOR_VERSION + 1, 0, 0)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_signature() {
+        let dir = TempDir::new("database-signature");
+        let (secret_key, public_key) = keypair(7);
+        let (_, other_key) = keypair(8);
+        save_both(&dir, &mut fixtures::vocab(), Some(&secret_key));
+
+        let options = VocabLoadOptions {
+            public_key: Some(public_key),
+            ..Default::default()
+        };
+        for res in load_both(&dir, &options) {
+            assert!(res.is_ok());
+        }
+
+        let options = VocabLoadOptions {
+            public_key: Some(other_key),
+            ..Default::default()
+        };
+        for res in load_both(&dir, &options) {
+            assert!(matches!(res, Err(Error::Load(LoadError::InvalidSignature))));
+        }
+
+        // A sealed but unsigned data store
+        save_both(&dir, &mut fixtures::vocab(), None);
+        let options = VocabLoadOptions {
+            public_key: Some(public_key),
+            ..Default::default()
+        };
+        for res in load_both(&dir, &options) {
+            assert!(matches!(res, Err(Error::Load(LoadError::MissingSignature))));
+        }
+    }
+}
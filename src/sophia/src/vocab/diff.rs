@@ -0,0 +1,388 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+use super::VocabDatabase;
+use crate::pos_tagger::{POSFeature, POSFeatureToken, POSModel, POSTag, POSWeight};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+/// The differences between two vocabulary data stores, such as two releases of `en.dat`.
+///
+/// Token and category IDs are not stable between releases, so all entries are compared by word, POS tag and category path.
+#[derive(Debug, Default)]
+pub struct VocabDiff {
+    pub words_added: Vec<String>,
+    pub words_removed: Vec<String>,
+    pub pos_changes: Vec<VocabDiffPOSChange>,
+    pub category_changes: Vec<VocabDiffCategoryChange>,
+    pub mwes_added: Vec<String>,
+    pub mwes_removed: Vec<String>,
+    pub hash_changes: Vec<VocabDiffHashChange>,
+    pub pos_models_added: Vec<String>,
+    pub pos_models_removed: Vec<String>,
+    pub pos_model_changes: Vec<VocabDiffPOSModelChange>,
+}
+
+/// A word within the wordlist whose set of POS tags changed.
+#[derive(Debug, Clone)]
+pub struct VocabDiffPOSChange {
+    pub word: String,
+    pub added: Vec<POSTag>,
+    pub removed: Vec<POSTag>,
+}
+
+/// A word and POS tag whose categories changed, with categories given as full paths.
+#[derive(Debug, Clone)]
+pub struct VocabDiffCategoryChange {
+    pub word: String,
+    pub pos: POSTag,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// A pre-processing hash that was added, removed or changed, with the tag and value before and after.
+#[derive(Debug, Clone)]
+pub struct VocabDiffHashChange {
+    pub word: String,
+    pub old: Option<(String, String)>,
+    pub new: Option<(String, String)>,
+}
+
+/// A word specific POS model present in both data stores that changed.
+///
+/// The drift ranges from 0.0 to 1.0, being the average difference in tag weights across all features of both models, with
+/// features only present in one model counted as fully changed.
+#[derive(Debug, Clone)]
+pub struct VocabDiffPOSModelChange {
+    pub word: String,
+    pub drift: f32,
+    pub target_tags_changed: bool,
+    pub features_added: usize,
+    pub features_removed: usize,
+    pub features_changed: usize,
+    pub conjunctions_changed: usize,
+}
+
+/// The minimum difference in a tag weight considered a change, ignoring rounding noise.
+const WEIGHT_EPSILON: f32 = 0.0001;
+
+impl VocabDiff {
+    /// Compares two vocabulary data stores, reporting all changes from the old to the new data store.
+    pub fn compile(old: &VocabDatabase, new: &VocabDatabase) -> Self {
+        let mut diff = Self::default();
+        diff.compare_wordlist(old, new);
+        diff.compare_tokens(old, new);
+        diff.compare_hashes(old, new);
+        diff.compare_pos_models(old, new);
+        diff
+    }
+
+    /// Checks whether the two data stores are identical in every compared aspect.
+    pub fn is_empty(&self) -> bool {
+        self.words_added.is_empty()
+            && self.words_removed.is_empty()
+            && self.pos_changes.is_empty()
+            && self.category_changes.is_empty()
+            && self.mwes_added.is_empty()
+            && self.mwes_removed.is_empty()
+            && self.hash_changes.is_empty()
+            && self.pos_models_added.is_empty()
+            && self.pos_models_removed.is_empty()
+            && self.pos_model_changes.is_empty()
+    }
+
+    /// Compares the words within the wordlist and their POS tags.
+    fn compare_wordlist(&mut self, old: &VocabDatabase, new: &VocabDatabase) {
+        let old_words = pos_sets(old);
+        let new_words = pos_sets(new);
+
+        for (word, new_tags) in new_words.iter() {
+            let Some(old_tags) = old_words.get(word) else {
+                self.words_added.push(word.to_string());
+                continue;
+            };
+            if old_tags == new_tags {
+                continue;
+            }
+
+            self.pos_changes.push(VocabDiffPOSChange {
+                word: word.to_string(),
+                added: new_tags.difference(old_tags).map(|tag| POSTag::from_str(tag)).collect(),
+                removed: old_tags.difference(new_tags).map(|tag| POSTag::from_str(tag)).collect(),
+            });
+        }
+
+        self.words_removed =
+            old_words.keys().filter(|word| !new_words.contains_key(*word)).cloned().collect();
+    }
+
+    /// Compares the MWEs and the categories assigned to each word and POS tag.
+    fn compare_tokens(&mut self, old: &VocabDatabase, new: &VocabDatabase) {
+        let old_tokens = token_categories(old);
+        let new_tokens = token_categories(new);
+
+        for ((word, tag), new_paths) in new_tokens.iter() {
+            let Some(old_paths) = old_tokens.get(&(word.to_string(), tag.to_string())) else {
+                continue;
+            };
+            if old_paths == new_paths {
+                continue;
+            }
+
+            self.category_changes.push(VocabDiffCategoryChange {
+                word: word.to_string(),
+                pos: POSTag::from_str(tag),
+                added: new_paths.difference(old_paths).cloned().collect(),
+                removed: old_paths.difference(new_paths).cloned().collect(),
+            });
+        }
+
+        // MWEs, by phrase regardless of POS tag
+        let old_mwes: BTreeSet<&String> =
+            old_tokens.keys().map(|(word, _)| word).filter(|word| word.contains(" ")).collect();
+        let new_mwes: BTreeSet<&String> =
+            new_tokens.keys().map(|(word, _)| word).filter(|word| word.contains(" ")).collect();
+        self.mwes_added = new_mwes.difference(&old_mwes).map(|word| word.to_string()).collect();
+        self.mwes_removed = old_mwes.difference(&new_mwes).map(|word| word.to_string()).collect();
+    }
+
+    /// Compares the pre-processing hashes.
+    fn compare_hashes(&mut self, old: &VocabDatabase, new: &VocabDatabase) {
+        let words: BTreeSet<&String> =
+            old.preprocess.hashes.keys().chain(new.preprocess.hashes.keys()).collect();
+
+        for word in words {
+            let old_hash = old.preprocess.hashes.get(word);
+            let new_hash = new.preprocess.hashes.get(word);
+            if old_hash != new_hash {
+                self.hash_changes.push(VocabDiffHashChange {
+                    word: word.to_string(),
+                    old: old_hash.cloned(),
+                    new: new_hash.cloned(),
+                });
+            }
+        }
+    }
+
+    /// Compares the word specific POS models, matched by the word and POS tag of the token they resolve.
+    fn compare_pos_models(&mut self, old: &VocabDatabase, new: &VocabDatabase) {
+        let old_models = pos_models(old);
+        let new_models = pos_models(new);
+
+        for (word, new_model) in new_models.iter() {
+            let Some(old_model) = old_models.get(word) else {
+                self.pos_models_added.push(word.to_string());
+                continue;
+            };
+
+            let change = ModelComparison::new(old, old_model, new, new_model).finish(word);
+            if change.drift > 0.0 || change.target_tags_changed || change.conjunctions_changed > 0 {
+                self.pos_model_changes.push(change);
+            }
+        }
+
+        self.pos_models_removed =
+            old_models.keys().filter(|word| !new_models.contains_key(*word)).cloned().collect();
+        self.pos_model_changes.sort_by(|a, b| b.drift.total_cmp(&a.drift));
+    }
+}
+
+/// Feature level comparison of two POS models, with features keyed by word rather than token ID.
+struct ModelComparison {
+    target_tags_changed: bool,
+    old_features: HashMap<String, POSWeight>,
+    new_features: HashMap<String, POSWeight>,
+    conjunctions_changed: usize,
+}
+
+impl ModelComparison {
+    /// Keys the features and conjunctions of both models, so they can be compared across data stores.
+    fn new(
+        old: &VocabDatabase,
+        old_model: &POSModel<i32>,
+        new: &VocabDatabase,
+        new_model: &POSModel<i32>,
+    ) -> Self {
+        let mut old_tags: Vec<String> = old_model.target_tags.iter().map(|t| t.to_str()).collect();
+        let mut new_tags: Vec<String> = new_model.target_tags.iter().map(|t| t.to_str()).collect();
+        old_tags.sort();
+        new_tags.sort();
+
+        let old_conjunctions: BTreeMap<String, usize> = old_model
+            .conjunctions
+            .iter()
+            .map(|(feature, list)| (feature_key(old, feature), list.len()))
+            .collect();
+        let new_conjunctions: BTreeMap<String, usize> = new_model
+            .conjunctions
+            .iter()
+            .map(|(feature, list)| (feature_key(new, feature), list.len()))
+            .collect();
+        let conjunctions_changed = old_conjunctions
+            .keys()
+            .chain(new_conjunctions.keys())
+            .collect::<BTreeSet<&String>>()
+            .into_iter()
+            .filter(|key| old_conjunctions.get(*key) != new_conjunctions.get(*key))
+            .count();
+
+        Self {
+            target_tags_changed: old_tags != new_tags,
+            old_features: old_model
+                .features
+                .iter()
+                .map(|(feature, weight)| (feature_key(old, feature), weight.clone()))
+                .collect(),
+            new_features: new_model
+                .features
+                .iter()
+                .map(|(feature, weight)| (feature_key(new, feature), weight.clone()))
+                .collect(),
+            conjunctions_changed,
+        }
+    }
+
+    /// Tallies the feature changes and drift of the model.
+    fn finish(self, word: &str) -> VocabDiffPOSModelChange {
+        let mut change = VocabDiffPOSModelChange {
+            word: word.to_string(),
+            drift: 0.0,
+            target_tags_changed: self.target_tags_changed,
+            features_added: 0,
+            features_removed: 0,
+            features_changed: 0,
+            conjunctions_changed: self.conjunctions_changed,
+        };
+
+        let keys: BTreeSet<&String> =
+            self.old_features.keys().chain(self.new_features.keys()).collect();
+        if keys.is_empty() {
+            return change;
+        }
+
+        let mut total = 0.0;
+        for key in keys.iter() {
+            match (self.old_features.get(*key), self.new_features.get(*key)) {
+                (Some(old_weight), Some(new_weight)) => {
+                    let distance = weight_distance(old_weight, new_weight);
+                    if distance > WEIGHT_EPSILON {
+                        change.features_changed += 1;
+                        total += distance;
+                    }
+                }
+                (None, _) => {
+                    change.features_added += 1;
+                    total += 1.0;
+                }
+                (_, None) => {
+                    change.features_removed += 1;
+                    total += 1.0;
+                }
+            }
+        }
+
+        change.drift = total / keys.len() as f32;
+        change
+    }
+}
+
+/// Returns the POS tags of every word within the wordlist.
+fn pos_sets(vocab: &VocabDatabase) -> BTreeMap<String, BTreeSet<String>> {
+    vocab
+        .iter_words()
+        .map(|(word, pos_map)| {
+            (
+                word.to_string(),
+                pos_map.keys().map(|tag| tag.to_str()).collect(),
+            )
+        })
+        .collect()
+}
+
+/// Returns the category paths of every token, keyed by lowercase word and POS tag.
+fn token_categories(vocab: &VocabDatabase) -> BTreeMap<(String, String), BTreeSet<String>> {
+    let mut res: BTreeMap<(String, String), BTreeSet<String>> = BTreeMap::new();
+    for (_, token) in vocab.iter_tokens() {
+        let paths = res.entry((token.word.to_lowercase(), token.pos.to_str())).or_default();
+        for category_id in token.categories.iter().chain(token.ner.iter()) {
+            paths.insert(
+                vocab.categories.id2path(*category_id).unwrap_or(format!("#{}", category_id)),
+            );
+        }
+    }
+    res
+}
+
+/// Returns the word specific POS models, keyed by the word and POS tag of the token they resolve (eg. `run/VB`).
+fn pos_models(vocab: &VocabDatabase) -> BTreeMap<String, &POSModel<i32>> {
+    vocab
+        .words
+        .pos_tagger
+        .words
+        .iter()
+        .map(|(token_id, model)| (token_key(vocab, *token_id), model))
+        .collect()
+}
+
+/// Returns the word and POS tag of a token ID, or the ID itself if it does not exist.
+fn token_key(vocab: &VocabDatabase, token_id: i32) -> String {
+    match vocab.get_token(token_id) {
+        Some(token) => format!("{}/{}", token.word.to_lowercase(), token.pos.to_str()),
+        None => format!("#{}", token_id),
+    }
+}
+
+/// Returns a key for a POS feature which is stable across data stores, with word features given by word instead of token ID.
+fn feature_key(vocab: &VocabDatabase, feature: &POSFeature<i32>) -> String {
+    let token = match &feature.feature_token {
+        POSFeatureToken::word(token_id) => format!("word({})", token_key(vocab, *token_id)),
+        other => format!("{:?}", other),
+    };
+    format!("{}@{}:{}", token, feature.offset, feature.noise_profile)
+}
+
+/// Returns the difference between two feature weights from 0.0 to 1.0, being half the total variation across all tags.
+fn weight_distance(old: &POSWeight, new: &POSWeight) -> f32 {
+    let tags: HashSet<&POSTag> = old.tags.keys().chain(new.tags.keys()).collect();
+    let total: f32 = tags
+        .into_iter()
+        .map(|tag| {
+            let a = old.tags.get(tag).copied().unwrap_or(0.0);
+            let b = new.tags.get(tag).copied().unwrap_or(0.0);
+            (a - b).abs()
+        })
+        .sum();
+
+    (total / 2.0).min(1.0)
+}
+
+impl fmt::Display for VocabDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Words added: {}", self.words_added.len())?;
+        writeln!(f, "Words removed: {}", self.words_removed.len())?;
+        writeln!(f, "POS changes: {}", self.pos_changes.len())?;
+        writeln!(f, "Category changes: {}", self.category_changes.len())?;
+        writeln!(f, "MWEs added: {}", self.mwes_added.len())?;
+        writeln!(f, "MWEs removed: {}", self.mwes_removed.len())?;
+        writeln!(f, "Hash changes: {}", self.hash_changes.len())?;
+        writeln!(f, "POS models added: {}", self.pos_models_added.len())?;
+        writeln!(f, "POS models removed: {}", self.pos_models_removed.len())?;
+        writeln!(f, "POS models changed: {}", self.pos_model_changes.len())?;
+
+        for change in self.pos_model_changes.iter() {
+            writeln!(
+                f,
+                "    {} drift {:.3} (+{} -{} ~{} features)",
+                change.word,
+                change.drift,
+                change.features_added,
+                change.features_removed,
+                change.features_changed
+            )?;
+        }
+        Ok(())
+    }
+}
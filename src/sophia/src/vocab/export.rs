@@ -0,0 +1,644 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+//! Export of the vocabulary data store into plain-text files for inspection.
+//!
+//! Tab delimited exports use the same file names and layout as the sources read by `VocabBuilder::from_dir()`,
+//! so an exported directory can be edited and rebuilt.  JSON Lines exports write one object per entry, including
+//! token IDs, for review and tooling, with token references given as `[word, pos]` pairs.  The POS tagger model is
+//! not exported, and must be set on the builder upon rebuild.
+//!
+//! Files written, with the extension `.txt` or `.jsonl`:
+//!
+//! ```text
+//! categories       category tree, one path per line in order of ID
+//! wordlist         each word with its POS tags and token IDs
+//! words            single word tokens with their attributes, in order of ID
+//! mwe              multi-word entities with their attributes and MWE type, in order of ID
+//! pronouns         word, category, sub-category, gender, person and number
+//! hashes           pre-processing hashes of word, tag and value
+//! future_verbs     future verb phrases, with the verb as a POS tag
+//! phrase_intents   intent and phrase
+//! ```
+
+use super::{FutureVerbPhrases, MWEType, PhraseIntents, VocabDatabase};
+use crate::error::Error;
+use crate::pos_tagger::POSTag;
+use crate::tokenizer::Token;
+use indexmap::IndexMap;
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// The format of an exported vocabulary, either JSON Lines or tab delimited builder sources.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ExportFormat {
+    jsonl,
+    tsv,
+}
+
+/// A single line of an exported file, serialized as a JSON object or tab delimited columns.
+trait ExportRecord: Serialize {
+    fn columns(&self) -> Vec<String>;
+}
+
+/// A category within the category tree.
+#[derive(Serialize)]
+struct CategoryRecord {
+    id: i16,
+    path: String,
+    depth: usize,
+    parent: Option<String>,
+}
+
+/// A word within the wordlist, with the token ID of each POS tag.
+#[derive(Serialize)]
+struct WordlistRecord {
+    word: String,
+    pos: IndexMap<String, i32>,
+}
+
+/// A word or MWE token, with its categories given as paths and all token references as word references.
+#[derive(Serialize)]
+struct TokenRecord {
+    id: i32,
+    word: String,
+    pos: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mwe_type: Option<String>,
+    stem: Option<WordReference>,
+    potential_stem: Vec<WordReference>,
+    categories: Vec<String>,
+    synonyms: Vec<WordReference>,
+    hypernyms: Vec<WordReference>,
+    hyponyms: Vec<WordReference>,
+    plural: Option<WordReference>,
+    flags: Vec<String>,
+}
+
+/// A reference to a token by its word and POS tag, serialized as a `[word, pos]` pair and written to builder sources as
+/// the word, qualified with its POS tag (eg. `run/VB`) when needed to resolve it.
+#[derive(Clone)]
+struct WordReference {
+    word: String,
+    pos: POSTag,
+    qualified: bool,
+}
+
+/// A pronoun with its linguistic properties.
+#[derive(Serialize)]
+struct PronounRecord {
+    word: String,
+    category: String,
+    sub_category: String,
+    gender: String,
+    person: String,
+    number: String,
+}
+
+/// A pre-processing hash of a word to its system tag and value.
+#[derive(Serialize)]
+struct HashRecord {
+    word: String,
+    tag: String,
+    value: String,
+}
+
+/// A future verb phrase, with the verb given as a POS tag.
+#[derive(Serialize)]
+struct FutureVerbRecord {
+    phrase: String,
+}
+
+/// A phrase intent, with the phrase given as words.
+#[derive(Serialize)]
+struct PhraseIntentRecord {
+    intent: String,
+    phrase: String,
+}
+
+impl VocabDatabase {
+    /// Exports the data store into the given directory as JSON Lines or tab delimited builder sources, returning the file names written.
+    ///
+    /// User dictionary entries are not exported.  Tab delimited exports can be rebuilt with `VocabBuilder::from_dir()`.
+    pub fn export(&self, dir: &str, format: ExportFormat) -> Result<Vec<String>, Error> {
+        fs::create_dir_all(dir)?;
+        let mut tokens: Vec<(i32, Token)> =
+            self.iter_tokens().map(|(id, token)| (id, token.into_owned())).collect();
+        tokens.sort_by_key(|(id, _)| *id);
+        let refs = ReferenceIndex::new(self);
+
+        let (words, mwes): (Vec<TokenRecord>, Vec<TokenRecord>) = tokens
+            .iter()
+            .map(|(id, token)| self.token_record(*id, token, &refs))
+            .partition(|record| record.mwe_type.is_none());
+
+        let files = vec![
+            write_records(dir, "categories", format, &self.category_records())?,
+            write_records(dir, "wordlist", format, &self.wordlist_records())?,
+            write_records(dir, "words", format, &words)?,
+            write_records(dir, "mwe", format, &mwes)?,
+            write_records(dir, "pronouns", format, &pronoun_records(&tokens))?,
+            write_records(dir, "hashes", format, &self.hash_records())?,
+            write_records(dir, "future_verbs", format, &self.future_verb_records())?,
+            write_records(
+                dir,
+                "phrase_intents",
+                format,
+                &self.phrase_intent_records(&refs),
+            )?,
+        ];
+
+        Ok(files)
+    }
+
+    /// Returns all categories ordered by ID, so parents precede their children.
+    fn category_records(&self) -> Vec<CategoryRecord> {
+        let mut ids: Vec<i16> = self.categories.nodes.keys().copied().collect();
+        ids.sort();
+
+        ids.into_iter()
+            .filter_map(|id| {
+                Some(CategoryRecord {
+                    id,
+                    path: self.categories.id2path(id)?,
+                    depth: self.categories.get_depth(id),
+                    parent: self
+                        .categories
+                        .get_parent(id)
+                        .and_then(|parent_id| self.categories.id2path(parent_id)),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns all words within the wordlist, sorted alphabetically.
+    fn wordlist_records(&self) -> Vec<WordlistRecord> {
+        let mut records: Vec<WordlistRecord> = self
+            .iter_words()
+            .map(|(word, pos_map)| WordlistRecord {
+                word: word.to_string(),
+                pos: pos_map.iter().map(|(tag, id)| (tag.to_str(), *id)).collect(),
+            })
+            .collect();
+        records.sort_by(|a, b| a.word.cmp(&b.word));
+        records
+    }
+
+    /// Converts a token into a record, resolving its categories to paths and token IDs to word references.
+    fn token_record(&self, id: i32, token: &Token, refs: &ReferenceIndex) -> TokenRecord {
+        let resolve = |ids: &[i32]| ids.iter().filter_map(|id| refs.get(*id)).collect();
+
+        let mut flags: Vec<String> = Vec::new();
+        for (flag, ids) in [
+            ("stop_word", &self.preprocess.stop_words),
+            ("auxillary_verb", &self.preprocess.auxillary_verbs),
+            ("predicative_verb", &self.preprocess.predicative_verbs),
+            ("infinitive_prefix", &self.preprocess.infinitive_prefixes),
+        ] {
+            if ids.contains(&id) {
+                flags.push(flag.to_string());
+            }
+        }
+        if token.is_name {
+            flags.push("name".to_string());
+        }
+
+        TokenRecord {
+            id,
+            word: token.word.to_string(),
+            pos: token.pos.to_str(),
+            mwe_type: self.mwe_type(&token.word),
+            stem: if token.stem == id {
+                None
+            } else {
+                refs.get(token.stem)
+            },
+            potential_stem: resolve(&token.potential_stem),
+            categories: token
+                .categories
+                .iter()
+                .chain(token.ner.iter())
+                .filter_map(|category_id| self.categories.id2path(*category_id))
+                .collect(),
+            synonyms: resolve(&token.synonyms),
+            hypernyms: resolve(&token.hypernyms),
+            hyponyms: resolve(&token.hyponyms),
+            plural: self.words.plural.get(&id).and_then(|plural_id| refs.get(*plural_id)),
+            flags,
+        }
+    }
+
+    /// Returns the MWE type of a multi-word token, or None for single words.
+    fn mwe_type(&self, word: &str) -> Option<String> {
+        if !word.contains(" ") {
+            return None;
+        }
+
+        let mut node = self.mwe_root();
+        for part in word.to_lowercase().split(" ") {
+            node = node.get_child(part)?;
+        }
+        let mwe_type = match node.mwe_type() {
+            MWEType::standard => "standard",
+            MWEType::scoring => "scoring",
+            MWEType::both => "both",
+        };
+        Some(mwe_type.to_string())
+    }
+
+    /// Returns all pre-processing hashes, sorted by word.
+    fn hash_records(&self) -> Vec<HashRecord> {
+        let mut records: Vec<HashRecord> = self
+            .preprocess
+            .hashes
+            .iter()
+            .map(|(word, (tag, value))| HashRecord {
+                word: word.to_string(),
+                tag: tag.to_string(),
+                value: value.to_string(),
+            })
+            .collect();
+        records.sort_by(|a, b| a.word.cmp(&b.word));
+        records
+    }
+
+    /// Returns all future verb phrases, sorted alphabetically.
+    fn future_verb_records(&self) -> Vec<FutureVerbRecord> {
+        let mut phrases: Vec<String> = Vec::new();
+        collect_future_verbs(&self.words.future_verbs, &mut Vec::new(), &mut phrases);
+        phrases.sort();
+        phrases.into_iter().map(|phrase| FutureVerbRecord { phrase }).collect()
+    }
+
+    /// Returns all phrase intents, sorted by intent then phrase.
+    fn phrase_intent_records(&self, refs: &ReferenceIndex) -> Vec<PhraseIntentRecord> {
+        let mut records: Vec<PhraseIntentRecord> = Vec::new();
+        collect_phrase_intents(
+            &self.words.phrase_intents,
+            &mut Vec::new(),
+            refs,
+            &mut records,
+        );
+        records.sort_by(|a, b| (&a.intent, &a.phrase).cmp(&(&b.intent, &b.phrase)));
+        records
+    }
+}
+
+/// Resolves token IDs into word references, only qualifying a word with its POS tag when it is not the word's first sense or contains a slash.
+struct ReferenceIndex {
+    words: HashMap<i32, WordReference>,
+}
+
+impl ReferenceIndex {
+    /// Indexes all tokens within the data store.
+    fn new(vocab: &VocabDatabase) -> Self {
+        let first_senses: HashMap<String, i32> = vocab
+            .iter_words()
+            .filter_map(|(word, pos_map)| Some((word.to_string(), *pos_map.values().next()?)))
+            .collect();
+
+        let words = vocab
+            .iter_tokens()
+            .map(|(id, token)| {
+                // The builder splits a reference at its last slash, so words containing one are always qualified
+                let key = token.word.to_lowercase();
+                let qualified = if key.contains(" ") {
+                    false
+                } else {
+                    key.contains("/") || first_senses.get(&key) != Some(&id)
+                };
+                let reference = WordReference {
+                    word: token.word.to_string(),
+                    pos: token.pos,
+                    qualified,
+                };
+                (id, reference)
+            })
+            .collect();
+
+        Self { words }
+    }
+
+    /// Returns the word reference of a token ID, or None if it does not exist.
+    fn get(&self, token_id: i32) -> Option<WordReference> {
+        self.words.get(&token_id).cloned()
+    }
+}
+
+impl Serialize for WordReference {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (&self.word, self.pos.to_str()).serialize(serializer)
+    }
+}
+
+impl fmt::Display for WordReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.qualified {
+            write!(f, "{}/{}", self.word, self.pos.to_str())
+        } else {
+            write!(f, "{}", self.word)
+        }
+    }
+}
+
+/// Collects all future verb phrases by walking the trie, restoring verb placeholders to their POS tag.
+fn collect_future_verbs(node: &FutureVerbPhrases, path: &mut Vec<String>, res: &mut Vec<String>) {
+    if node.is_complete {
+        res.push(path.join(" "));
+    }
+
+    for (word, child) in node.children.iter() {
+        path.push(child.expected_verb_pos.clone().unwrap_or(word.to_string()));
+        collect_future_verbs(child, path, res);
+        path.pop();
+    }
+}
+
+/// Collects all phrase intents by walking the trie, resolving each token ID to its word reference.
+fn collect_phrase_intents(
+    node: &PhraseIntents,
+    path: &mut Vec<i32>,
+    refs: &ReferenceIndex,
+    res: &mut Vec<PhraseIntentRecord>,
+) {
+    if let Some(intent) = node.intent {
+        let words: Option<Vec<String>> =
+            path.iter().map(|id| refs.get(*id).map(|r| r.to_string())).collect();
+        if let Some(words) = words {
+            res.push(PhraseIntentRecord {
+                intent: intent.to_string(),
+                phrase: words.join(" "),
+            });
+        }
+    }
+
+    for (token_id, child) in node.children.iter() {
+        path.push(*token_id);
+        collect_phrase_intents(child, path, refs, res);
+        path.pop();
+    }
+}
+
+/// Returns one pronoun per word, as the builder assigns each pronoun to all pronoun senses of its word.
+fn pronoun_records(tokens: &[(i32, Token)]) -> Vec<PronounRecord> {
+    let mut records: Vec<PronounRecord> = Vec::new();
+    for (_, token) in tokens.iter() {
+        let Some(pronoun) = &token.pronoun else {
+            continue;
+        };
+        let word = token.word.to_lowercase();
+        if records.iter().any(|r| r.word == word) {
+            continue;
+        }
+
+        records.push(PronounRecord {
+            word,
+            category: format!("{:?}", pronoun.category),
+            sub_category: format!("{:?}", pronoun.sub_category),
+            gender: format!("{:?}", pronoun.gender),
+            person: format!("{:?}", pronoun.person),
+            number: format!("{:?}", pronoun.number),
+        });
+    }
+    records
+}
+
+/// Writes records to `{name}.jsonl` or `{name}.txt` within the directory, returning the file name written.
+fn write_records<T: ExportRecord>(
+    dir: &str,
+    name: &str,
+    format: ExportFormat,
+    records: &[T],
+) -> Result<String, Error> {
+    let filename = match format {
+        ExportFormat::jsonl => format!("{}.jsonl", name),
+        ExportFormat::tsv => format!("{}.txt", name),
+    };
+    let mut writer = BufWriter::new(File::create(Path::new(dir).join(&filename))?);
+
+    for record in records.iter() {
+        let line = match format {
+            ExportFormat::jsonl => match serde_json::to_string(record) {
+                Ok(r) => r,
+                Err(e) => {
+                    return Err(Error::Save(format!("Unable to export {}, {}", name, e)));
+                }
+            },
+            ExportFormat::tsv => record
+                .columns()
+                .iter()
+                .map(|column| column.replace(['\t', '\r', '\n'], " "))
+                .collect::<Vec<String>>()
+                .join("\t"),
+        };
+        writeln!(writer, "{}", line)?;
+    }
+    writer.flush()?;
+
+    Ok(filename)
+}
+
+/// Formats a builder attribute, or None if the value is empty.
+fn attribute(key: &str, values: &[String]) -> Option<String> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(format!("{}={}", key, values.join(",")))
+}
+
+/// Formats word references as they are written to builder sources.
+fn references(values: &[WordReference]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+impl ExportRecord for CategoryRecord {
+    fn columns(&self) -> Vec<String> {
+        vec![self.path.to_string()]
+    }
+}
+
+impl ExportRecord for WordlistRecord {
+    fn columns(&self) -> Vec<String> {
+        let pos: Vec<String> = self.pos.iter().map(|(tag, id)| format!("{}={}", tag, id)).collect();
+        vec![self.word.to_string(), pos.join(",")]
+    }
+}
+
+impl ExportRecord for TokenRecord {
+    fn columns(&self) -> Vec<String> {
+        let mut columns = vec![self.word.to_string(), self.pos.to_string()];
+        let attributes = [
+            attribute("stem", &references(self.stem.as_slice())),
+            attribute("potential_stem", &references(&self.potential_stem)),
+            attribute("categories", &self.categories),
+            attribute("synonyms", &references(&self.synonyms)),
+            attribute("hypernyms", &references(&self.hypernyms)),
+            attribute("hyponyms", &references(&self.hyponyms)),
+            attribute("plural", &references(self.plural.as_slice())),
+            attribute("flags", &self.flags),
+            attribute("type", self.mwe_type.as_slice()),
+        ];
+        columns.extend(attributes.into_iter().flatten());
+        columns
+    }
+}
+
+impl ExportRecord for PronounRecord {
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.word.to_string(),
+            self.category.to_string(),
+            self.sub_category.to_string(),
+            self.gender.to_string(),
+            self.person.to_string(),
+            self.number.to_string(),
+        ]
+    }
+}
+
+impl ExportRecord for HashRecord {
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.word.to_string(),
+            self.tag.to_string(),
+            self.value.to_string(),
+        ]
+    }
+}
+
+impl ExportRecord for FutureVerbRecord {
+    fn columns(&self) -> Vec<String> {
+        vec![self.phrase.to_string()]
+    }
+}
+
+impl ExportRecord for PhraseIntentRecord {
+    fn columns(&self) -> Vec<String> {
+        vec![self.intent.to_string(), self.phrase.to_string()]
+    }
+}
+
+impl TryFrom<&str> for ExportFormat {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let res = match value.to_lowercase().as_str() {
+            "jsonl" | "json" => Self::jsonl,
+            "tsv" | "txt" => Self::tsv,
+            _ => return Err(Error::Generic(format!("Invalid export format, {}", value))),
+        };
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::VocabBuilder;
+    use crate::vocab::fixtures::{self, TempDir};
+
+    /// Words containing slashes, including one ending in a valid POS tag.
+    static SLASH_WORDS: &str = "\
+and/or\tCC
+DVD/CD\tNN\tsynonyms=and/or
+24/7\tJJ
+24/7\tRB\tstem=24/7/JJ
+nonstop\tJJ\tsynonyms=24/7/JJ\thypernyms=24/7/RB,DVD/CD/NN
+";
+
+    /// Builds the sample vocabulary with the words containing slashes.
+    fn slash_vocab() -> VocabDatabase {
+        let mut builder = fixtures::builder();
+        builder.add_words(SLASH_WORDS).unwrap();
+        builder.build().unwrap()
+    }
+
+    /// Resolves token IDs into their word and POS tag, as token IDs change upon rebuild.
+    fn resolve(vocab: &VocabDatabase, ids: &[i32]) -> Vec<(String, POSTag)> {
+        ids.iter()
+            .map(|id| {
+                let token = vocab.get_token(*id).unwrap();
+                (token.word, token.pos)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tsv_round_trip() {
+        let dir = TempDir::new("export-tsv");
+        let vocab = slash_vocab();
+        vocab.export(dir.path(), ExportFormat::tsv).unwrap();
+
+        // Words containing a slash are always qualified, as the builder splits at the last slash
+        let words = fs::read_to_string(dir.file("words.txt")).unwrap();
+        assert!(words.contains("DVD/CD\tNN\tsynonyms=and/or/CC\n"));
+        assert!(words.contains("nonstop\tJJ\tsynonyms=24/7/JJ\thypernyms=24/7/RB,DVD/CD/NN\n"));
+        assert!(words.contains("cars\tNNS\tstem=car\t"));
+        assert!(words.contains("run\tNN\n"));
+
+        let rebuilt = VocabBuilder::from_dir(dir.path(), "en").unwrap().build().unwrap();
+        assert_eq!(rebuilt.words.id2token.len(), vocab.words.id2token.len());
+        for token in vocab.words.id2token.values() {
+            let id = match token.word.contains(" ") {
+                true => rebuilt.words.mwe.get(&token.word),
+                false => rebuilt.words.wordlist[&token.word.to_lowercase()][&token.pos],
+            };
+            let other = rebuilt.get_token(id).unwrap();
+            assert_eq!(other.pos, token.pos, "{}", token.word);
+            assert_eq!(
+                resolve(&rebuilt, &[other.stem]),
+                resolve(&vocab, &[token.stem])
+            );
+            assert_eq!(
+                resolve(&rebuilt, &other.synonyms),
+                resolve(&vocab, &token.synonyms)
+            );
+            assert_eq!(
+                resolve(&rebuilt, &other.hypernyms),
+                resolve(&vocab, &token.hypernyms)
+            );
+            assert_eq!(
+                resolve(&rebuilt, &other.hyponyms),
+                resolve(&vocab, &token.hyponyms)
+            );
+        }
+    }
+
+    #[test]
+    fn test_jsonl_references() {
+        let dir = TempDir::new("export-jsonl");
+        slash_vocab().export(dir.path(), ExportFormat::jsonl).unwrap();
+
+        let words = fs::read_to_string(dir.file("words.jsonl")).unwrap();
+        let records: Vec<serde_json::Value> =
+            words.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        let record = |word: &str, pos: &str| {
+            records.iter().find(|r| r["word"] == word && r["pos"] == pos).unwrap().clone()
+        };
+
+        // References are [word, pos] pairs, regardless of whether the word is qualified when written as a source
+        let nonstop = record("nonstop", "JJ");
+        assert_eq!(nonstop["synonyms"], serde_json::json!([["24/7", "JJ"]]));
+        assert_eq!(
+            nonstop["hypernyms"],
+            serde_json::json!([["24/7", "RB"], ["DVD/CD", "NN"]])
+        );
+        assert_eq!(
+            record("24/7", "RB")["stem"],
+            serde_json::json!(["24/7", "JJ"])
+        );
+        assert_eq!(
+            record("cars", "NNS")["stem"],
+            serde_json::json!(["car", "NN"])
+        );
+        assert_eq!(record("car", "NN")["stem"], serde_json::Value::Null);
+    }
+}
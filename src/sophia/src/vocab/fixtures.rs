@@ -0,0 +1,143 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+//! A small sample vocabulary shared by unit tests, built from the same plain-text sources as a full data store.
+
+use super::{VocabBuilder, VocabDatabase};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static TEMP_DIR_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+pub(crate) static CATEGORIES: &str = "\
+nouns/transportation/automobile
+nouns/transportation/aircraft
+verbs/action/movement
+ner/person
+";
+
+pub(crate) static WORDS: &str = "\
+|nl|\tSS
+.\tSS
+,\tPUNC
+|num|\tSYS
+the\tDT\tflags=stop_word
+a\tDT\tflags=stop_word
+i\tPRP
+he\tPRP
+will\tMD
+have\tVB\tflags=auxillary_verb
+go\tVB\tcategories=verbs/action/movement
+goes\tVBZ\tstem=go/VB
+went\tVBD\tstem=go/VB
+gone\tVBN\tstem=go/VB
+going\tVBG\tstem=go/VB
+car\tNN\tcategories=nouns/transportation/automobile\tplural=cars\thypernyms=vehicle\tsynonyms=automobile
+cars\tNNS\tstem=car\tcategories=nouns/transportation/automobile
+automobile\tNN\tcategories=nouns/transportation/automobile\tsynonyms=car\tplural=automobiles
+automobiles\tNNS\tstem=automobile
+vehicle\tNN\thyponyms=car,plane\thypernyms=object
+plane\tNN\tcategories=nouns/transportation/aircraft\thypernyms=vehicle
+new\tJJ
+york\tNNP
+city\tNN
+matt\tNNP\tcategories=ner/person\tflags=name
+run\tVB\tcategories=verbs/action/movement
+run\tNN
+yes\tUH
+please\tUH
+quickly\tRB
+object\tNN
+truck\tNN\thypernyms=motor vehicle\tcategories=nouns/transportation/automobile
+good\tJJ
+better\tJJR\tstem=good/JJ
+best\tJJS\tstem=good/JJ
+mouse\tNN\tplural=mice
+mice\tNNS\tstem=mouse
+to\tIN
+be\tVB
+is\tVBZ\tstem=be/VB
+";
+
+pub(crate) static MWES: &str = "\
+new york\tNNP
+new york city\tNNP
+motor vehicle\tNN\ttype=both\thypernyms=vehicle
+";
+
+pub(crate) static PRONOUNS: &str = "\
+i\tpersonal\tnone\tneutral\tfirst\tsingular
+he\tpersonal\tnone\tmale\tthird\tsingular
+";
+
+pub(crate) static HASHES: &str = "\
+can't\t|contraction|\tcan not
+five\t|num|\t5
+";
+
+pub(crate) static FUTURE_VERBS: &str = "\
+will VB
+will have VBN
+";
+
+pub(crate) static PHRASE_INTENTS: &str = "\
+affirmation\tyes please
+";
+
+/// A uniquely named temporary directory, removed along with its contents when dropped.
+pub(crate) struct TempDir {
+    path: PathBuf,
+}
+
+/// Returns a builder with all sample sources added.
+pub(crate) fn builder() -> VocabBuilder {
+    let mut builder = VocabBuilder::new("en");
+    builder.add_categories(CATEGORIES).unwrap();
+    builder.add_words(WORDS).unwrap();
+    builder.add_mwes(MWES).unwrap();
+    builder.add_pronouns(PRONOUNS).unwrap();
+    builder.add_hashes(HASHES).unwrap();
+    builder.add_future_verbs(FUTURE_VERBS).unwrap();
+    builder.add_phrase_intents(PHRASE_INTENTS).unwrap();
+    builder
+}
+
+/// Builds the sample vocabulary.
+pub(crate) fn vocab() -> VocabDatabase {
+    builder().build().unwrap()
+}
+
+impl TempDir {
+    /// Creates a new, empty temporary directory.
+    pub(crate) fn new(name: &str) -> Self {
+        let counter = TEMP_DIR_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!(
+            "sophia-{}-{}-{}",
+            name,
+            std::process::id(),
+            counter
+        ));
+        fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+
+    /// Returns the path of the directory.
+    pub(crate) fn path(&self) -> &str {
+        self.path.to_str().unwrap()
+    }
+
+    /// Returns the path of a file within the directory.
+    pub(crate) fn file(&self, filename: &str) -> String {
+        self.path.join(filename).to_str().unwrap().to_string()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+use super::VocabDatabase;
+use crate::tokenizer::Token;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+impl VocabDatabase {
+    /// Returns all hypernyms of a token with their shortest distance, nearest first.
+    pub fn get_hypernyms(&self, token_id: i32) -> Vec<(i32, usize)> {
+        self.walk(token_id, usize::MAX, |token| &token.hypernyms)
+    }
+
+    /// Returns the hypernym chain of a token up to its root, following the first hypernym at each level and stopping upon a cycle.
+    pub fn get_hypernym_chain(&self, token_id: i32) -> Vec<i32> {
+        let mut chain: Vec<i32> = Vec::new();
+        let mut visited: HashSet<i32> = HashSet::from([token_id]);
+
+        let mut current = token_id;
+        while let Some(next) = self.get_token(current).and_then(|t| t.hypernyms.first().copied()) {
+            if !visited.insert(next) {
+                break;
+            }
+            chain.push(next);
+            current = next;
+        }
+
+        chain
+    }
+
+    /// Returns all hyponyms of a token down to the maximum depth with their shortest distance, nearest first.
+    pub fn get_hyponyms(&self, token_id: i32, max_depth: usize) -> Vec<(i32, usize)> {
+        self.walk(token_id, max_depth, |token| &token.hyponyms)
+    }
+
+    /// Checks whether a token is a kind of the ancestor token, being either the same token or one of its hypernyms.
+    pub fn is_a(&self, token_id: i32, ancestor_id: i32) -> bool {
+        token_id == ancestor_id
+            || self.get_hypernyms(token_id).iter().any(|(id, _)| *id == ancestor_id)
+    }
+
+    /// Returns the hypernyms shared by two tokens, including either token itself, ordered by the combined distance from both.
+    pub fn get_shared_hypernyms(&self, token_id1: i32, token_id2: i32) -> Vec<i32> {
+        let distances1: HashMap<i32, usize> =
+            self.get_hypernyms(token_id1).into_iter().chain([(token_id1, 0)]).collect();
+
+        let mut shared: Vec<(i32, usize)> = self
+            .get_hypernyms(token_id2)
+            .into_iter()
+            .chain([(token_id2, 0)])
+            .filter_map(|(id, d2)| distances1.get(&id).map(|d1| (id, d1 + d2)))
+            .collect();
+
+        shared.sort_by_key(|(id, distance)| (*distance, *id));
+        shared.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// Finds a cycle within the hypernym graph reachable from a token, returning the token IDs that form it.
+    pub fn find_hypernym_cycle(&self, token_id: i32) -> Option<Vec<i32>> {
+        let mut stack: Vec<(i32, Vec<i32>)> = vec![(token_id, self.hypernyms_of(token_id))];
+        let mut finished: HashSet<i32> = HashSet::new();
+
+        // Depth-first, with the current path held on the stack
+        while let Some((_, pending)) = stack.last_mut() {
+            let Some(next) = pending.pop() else {
+                let (id, _) = stack.pop().unwrap();
+                finished.insert(id);
+                continue;
+            };
+
+            if let Some(pos) = stack.iter().position(|(id, _)| *id == next) {
+                return Some(stack[pos..].iter().map(|(id, _)| *id).collect());
+            }
+            if !finished.contains(&next) {
+                stack.push((next, self.hypernyms_of(next)));
+            }
+        }
+
+        None
+    }
+
+    /// Walks the hypernym or hyponym graph breadth-first, skipping visited IDs so cycles terminate.
+    fn walk(
+        &self,
+        token_id: i32,
+        max_depth: usize,
+        edges: fn(&Token) -> &Vec<i32>,
+    ) -> Vec<(i32, usize)> {
+        let mut res: Vec<(i32, usize)> = Vec::new();
+        let mut visited: HashSet<i32> = HashSet::from([token_id]);
+        let mut queue: VecDeque<(i32, usize)> = VecDeque::from([(token_id, 0)]);
+
+        while let Some((id, depth)) = queue.pop_front() {
+            if depth >= max_depth {
+                continue;
+            }
+            let Some(token) = self.get_token(id) else {
+                continue;
+            };
+
+            for next in edges(&token).iter() {
+                if visited.insert(*next) {
+                    res.push((*next, depth + 1));
+                    queue.push_back((*next, depth + 1));
+                }
+            }
+        }
+
+        res
+    }
+
+    /// Returns the direct hypernyms of a token, or an empty vector if it does not exist.
+    fn hypernyms_of(&self, token_id: i32) -> Vec<i32> {
+        match self.get_token(token_id) {
+            Some(token) => token.hypernyms,
+            None => Vec::new(),
+        }
+    }
+}
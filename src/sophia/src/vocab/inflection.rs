@@ -0,0 +1,459 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+use super::VocabDatabase;
+use crate::pos_tagger::POSTag;
+use crate::tokenizer::Token;
+use std::collections::HashMap;
+
+/// The word classes supported by inflection.
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum WordClass {
+    verb,
+    noun,
+    adjective,
+}
+
+impl VocabDatabase {
+    /// Inflects a word into the target POS tag (VB, VBD, VBG, VBN, VBP, VBZ, NN, NNS, JJ, JJR or JJS), or None if the tag is not supported.
+    ///
+    /// Forms within the vocabulary are used first, falling back to English morphological rules with unknown words treated as their base form.
+    pub fn inflect(&self, word: &str, target: POSTag) -> Option<String> {
+        self.inflect_token(&Token::new(word, self), target)
+    }
+
+    /// Inflects a token into the target POS tag, switching to another part of speech of the same word if needed.
+    pub fn inflect_token(&self, token: &Token, target: POSTag) -> Option<String> {
+        let class = WordClass::from_tag(target)?;
+
+        // Unknown within this word class, so apply rules
+        let Some(sense) = self.get_sense(token, class) else {
+            let res = apply_rules(&token.word.to_lowercase(), target);
+            return Some(match_case(&res, &token.word));
+        };
+        if sense.pos == target {
+            return Some(match_case(&sense.word, &token.word));
+        }
+
+        // Get from base form
+        let base = self.get_base_form(&sense);
+        let res = if base.pos == target {
+            base.word.to_string()
+        } else if let Some(form) = self.get_inflected_form(base.index, target) {
+            form.word
+        } else {
+            apply_rules(&base.word.to_lowercase(), target)
+        };
+
+        Some(match_case(&res, &token.word))
+    }
+
+    /// Returns the lemma of a token based on its POS tag, resolving MWE verb phrases (eg. will have gone -> go), possessives and spelling corrections.
+    ///
+    /// Tokens not within the vocabulary have their inflectional suffixes removed using English morphological rules.
+    pub fn lemmatize(&self, token: &Token) -> String {
+        if token.pos == POSTag::SYS || token.is_punctuation() {
+            return token.word.to_string();
+        }
+        let word = token
+            .word
+            .trim_end_matches("'s")
+            .trim_end_matches("\u{2019}s")
+            .trim_end_matches(['\'', '\u{2019}']);
+
+        // Get token matching the assigned POS tag
+        let token_id = self
+            .get_pos_map(&word.to_lowercase())
+            .and_then(|pos_map| pos_map.get(&token.pos).copied())
+            .unwrap_or(token.index);
+        if token_id > 0 {
+            return self.get_base_form(&self.from_int(token_id)).word;
+        }
+
+        // Unknown word, with verb phrases ending in the verb
+        let last = word.rsplit(' ').next().unwrap_or(word);
+        if token.is_named_entity() {
+            lemma_rules(last, token.pos)
+        } else {
+            lemma_rules(&last.to_lowercase(), token.pos)
+        }
+    }
+
+    /// Gets the part of speech of a token within the word class, or None if the word has none.
+    fn get_sense(&self, token: &Token, class: WordClass) -> Option<Token> {
+        if token.index > 0 && class.contains(token.pos) {
+            return Some(token.clone());
+        }
+
+        let pos_map = self.get_pos_map(&token.word.to_lowercase())?;
+        let (_, token_id) = pos_map.iter().find(|(pos, _)| class.contains(**pos))?;
+        Some(self.from_int(*token_id))
+    }
+
+    /// Gets the base form of a token via its stem, or the plural mapping for plural nouns.
+    fn get_base_form(&self, token: &Token) -> Token {
+        if token.stem > 0 && token.stem != token.index {
+            return self.from_int(token.stem);
+        }
+        if token.pos == POSTag::NNS
+            && let Some(singular) = self.plural_index().get(&token.index)
+        {
+            return self.from_int(*singular);
+        }
+
+        token.clone()
+    }
+
+    /// Gets the inflected form of a base token ID with the target POS tag from the vocabulary, if one exists.
+    fn get_inflected_form(&self, base_id: i32, target: POSTag) -> Option<Token> {
+        if target == POSTag::NNS
+            && let Some(plural) = self.words.plural.get(&base_id)
+        {
+            return Some(self.from_int(*plural));
+        }
+
+        let forms = self.stem_index().get(&base_id)?;
+        forms.iter().map(|id| self.from_int(*id)).find(|token| token.pos == target)
+    }
+
+    /// Returns the inverted index of stem IDs to the IDs of tokens derived from them, building it upon first use.
+    fn stem_index(&self) -> &HashMap<i32, Vec<i32>> {
+        self.words.stem_index.get_or_init(|| {
+            let mut index: HashMap<i32, Vec<i32>> = HashMap::new();
+            for (token_id, token) in self.iter_tokens() {
+                if token.stem > 0 && token.stem != token_id {
+                    index.entry(token.stem).or_default().push(token_id);
+                }
+            }
+            index
+        })
+    }
+
+    /// Returns the inverted index of plural token IDs to their singular token IDs, building it upon first use.
+    fn plural_index(&self) -> &HashMap<i32, i32> {
+        self.words.plural_index.get_or_init(|| {
+            let mut index: HashMap<i32, i32> = HashMap::new();
+            for (singular, plural) in self.words.plural.iter() {
+                index.entry(*plural).or_insert(*singular);
+            }
+            index
+        })
+    }
+}
+
+impl WordClass {
+    /// Gets the word class of an inflectable POS tag.
+    fn from_tag(tag: POSTag) -> Option<Self> {
+        match tag {
+            POSTag::VB | POSTag::VBD | POSTag::VBG | POSTag::VBN | POSTag::VBP | POSTag::VBZ => {
+                Some(Self::verb)
+            }
+            POSTag::NN | POSTag::NNS => Some(Self::noun),
+            POSTag::JJ | POSTag::JJR | POSTag::JJS => Some(Self::adjective),
+            _ => None,
+        }
+    }
+
+    /// Checks whether the POS tag belongs to the word class.
+    fn contains(&self, tag: POSTag) -> bool {
+        Self::from_tag(tag) == Some(*self)
+    }
+}
+
+/// Inflects a base form into the target POS tag using English morphological rules.
+fn apply_rules(word: &str, target: POSTag) -> String {
+    match target {
+        POSTag::VBZ if ends_consonant_then(word, 'o') => format!("{}es", word),
+        POSTag::VBZ => add_s(word),
+        POSTag::VBG => present_participle(word),
+        POSTag::VBD | POSTag::VBN => past_tense(word),
+        POSTag::NNS if word.ends_with("fe") => format!("{}ves", &word[..word.len() - 2]),
+        POSTag::NNS => add_s(word),
+        POSTag::NN => singularize(word),
+        POSTag::JJR => compare(word, "er", "more"),
+        POSTag::JJS => compare(word, "est", "most"),
+        _ => word.to_string(),
+    }
+}
+
+/// Removes inflectional suffixes from a word using English morphological rules, based on its POS tag.
+fn lemma_rules(word: &str, pos: POSTag) -> String {
+    match pos {
+        POSTag::NNS | POSTag::NNPS => singularize(word),
+        POSTag::VBZ if word.ends_with("oes") => word[..word.len() - 2].to_string(),
+        POSTag::VBZ => singularize(word),
+        POSTag::VBG => remove_suffix(word, "ing"),
+        POSTag::VBD | POSTag::VBN => remove_suffix(word, "ed"),
+        POSTag::JJR | POSTag::RBR => remove_suffix(word, "er"),
+        POSTag::JJS | POSTag::RBS => remove_suffix(word, "est"),
+        _ => word.to_string(),
+    }
+}
+
+/// Removes a verb or adjective suffix, restoring any final 'y' or silent 'e' and undoing a doubled final consonant.
+fn remove_suffix(word: &str, suffix: &str) -> String {
+    let Some(stem) = word.strip_suffix(suffix).filter(|stem| stem.len() > 1) else {
+        return word.to_string();
+    };
+
+    // lying, tried, happiest
+    if suffix == "ing" {
+        if stem.len() == 2 && stem.ends_with('y') {
+            return format!("{}ie", &stem[..1]);
+        }
+    } else if let Some(prefix) = stem.strip_suffix('i') {
+        return format!("{}y", prefix);
+    } else if stem.ends_with('e') {
+        return format!("{}e", stem);
+    }
+
+    // stopped, running, bigger
+    let mut chars = stem.chars().rev();
+    if let (Some(c1), Some(c2)) = (chars.next(), chars.next())
+        && c1 == c2
+        && c1.is_alphabetic()
+        && !is_vowel(c1)
+        && !"lsz".contains(c1)
+    {
+        return stem[..stem.len() - c1.len_utf8()].to_string();
+    }
+
+    // hoped, making, nicer
+    if is_cvc(stem) {
+        return format!("{}e", stem);
+    }
+
+    stem.to_string()
+}
+
+/// Adds an 's' suffix, with 'es' after sibilants and 'ies' after a consonant and 'y'.
+fn add_s(word: &str) -> String {
+    if ["s", "x", "z", "ch", "sh"].iter().any(|suffix| word.ends_with(suffix)) {
+        format!("{}es", word)
+    } else if ends_consonant_then(word, 'y') {
+        format!("{}ies", &word[..word.len() - 1])
+    } else {
+        format!("{}s", word)
+    }
+}
+
+/// Removes the plural suffix of a noun.
+fn singularize(word: &str) -> String {
+    if word.len() > 4 && word.ends_with("ies") {
+        format!("{}y", &word[..word.len() - 3])
+    } else if ["sses", "ches", "shes", "xes", "zzes"].iter().any(|suffix| word.ends_with(suffix)) {
+        word[..word.len() - 2].to_string()
+    } else if word.ends_with('s') && !["ss", "us", "is"].iter().any(|suffix| word.ends_with(suffix))
+    {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+/// Adds the 'ing' suffix of the present participle.
+fn present_participle(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ie") {
+        format!("{}ying", stem)
+    } else if word.len() > 2
+        && word.ends_with('e')
+        && !["ee", "ye", "oe"].iter().any(|suffix| word.ends_with(suffix))
+    {
+        format!("{}ing", &word[..word.len() - 1])
+    } else {
+        format!("{}ing", double_final(word))
+    }
+}
+
+/// Adds the 'ed' suffix of the past tense and past participle.
+fn past_tense(word: &str) -> String {
+    if word.ends_with('e') {
+        format!("{}d", word)
+    } else if ends_consonant_then(word, 'y') {
+        format!("{}ied", &word[..word.len() - 1])
+    } else {
+        format!("{}ed", double_final(word))
+    }
+}
+
+/// Adds a comparative or superlative suffix, or prefixes the adverb for adjectives of more than one syllable not ending in 'y'.
+fn compare(word: &str, suffix: &str, adverb: &str) -> String {
+    let syllables = count_syllables(word);
+    if syllables > 2 || (syllables == 2 && !ends_consonant_then(word, 'y')) {
+        format!("{} {}", adverb, word)
+    } else if word.ends_with('e') {
+        format!("{}{}", word, &suffix[1..])
+    } else if ends_consonant_then(word, 'y') {
+        format!("{}i{}", &word[..word.len() - 1], suffix)
+    } else {
+        format!("{}{}", double_final(word), suffix)
+    }
+}
+
+/// Doubles the final consonant of single syllable words ending in consonant-vowel-consonant (eg. stop, big).
+fn double_final(word: &str) -> String {
+    match word.chars().last() {
+        Some(c) if is_cvc(word) => format!("{}{}", word, c),
+        _ => word.to_string(),
+    }
+}
+
+/// Checks whether a single syllable word ends in consonant-vowel-consonant, with the final consonant not being 'w', 'x' or 'y'.
+fn is_cvc(word: &str) -> bool {
+    let chars: Vec<char> = word.chars().collect();
+    let Some([a, b, c]) =
+        chars.len().checked_sub(3).map(|x| [chars[x], chars[x + 1], chars[x + 2]])
+    else {
+        return false;
+    };
+
+    !is_vowel(a) && is_vowel(b) && !is_vowel(c) && !"wxy".contains(c) && count_syllables(word) == 1
+}
+
+/// Checks whether the word ends in a consonant followed by the given letter.
+fn ends_consonant_then(word: &str, last: char) -> bool {
+    let mut chars = word.chars().rev();
+    chars.next() == Some(last) && chars.next().is_some_and(|c| c.is_alphabetic() && !is_vowel(c))
+}
+
+/// Estimates the number of syllables by counting vowel groups, ignoring a silent final 'e'.
+fn count_syllables(word: &str) -> usize {
+    let mut count = 0;
+    let mut prev_vowel = false;
+    for (x, c) in word.chars().enumerate() {
+        let vowel = is_vowel(c) || (c == 'y' && x > 0);
+        if vowel && !prev_vowel {
+            count += 1;
+        }
+        prev_vowel = vowel;
+    }
+
+    if count > 1 && word.ends_with('e') && !word.ends_with("le") && !word.ends_with("ee") {
+        count -= 1;
+    }
+    count.max(1)
+}
+
+/// Checks whether the character is a vowel.
+fn is_vowel(c: char) -> bool {
+    "aeiou".contains(c)
+}
+
+/// Matches the capitalization of the result to the original word, being uppercase or a leading capital.
+fn match_case(word: &str, orig: &str) -> String {
+    if orig.chars().count() > 1 && orig.chars().all(|c| !c.is_lowercase()) {
+        word.to_uppercase()
+    } else if orig.chars().next().is_some_and(|c| c.is_uppercase()) {
+        let mut chars = word.chars();
+        chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+    } else {
+        word.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::fixtures;
+
+    #[test]
+    fn test_inflect() {
+        let vocab = fixtures::vocab();
+        let cases = [
+            // Regular verbs
+            ("walk", POSTag::VBZ, "walks"),
+            ("walk", POSTag::VBD, "walked"),
+            ("walk", POSTag::VBG, "walking"),
+            ("walk", POSTag::VBN, "walked"),
+            ("visit", POSTag::VBD, "visited"),
+            ("push", POSTag::VBZ, "pushes"),
+            ("veto", POSTag::VBZ, "vetoes"),
+            // Doubled final consonant
+            ("stop", POSTag::VBD, "stopped"),
+            ("stop", POSTag::VBG, "stopping"),
+            ("stop", POSTag::VBZ, "stops"),
+            ("fix", POSTag::VBD, "fixed"),
+            // Final -y
+            ("try", POSTag::VBZ, "tries"),
+            ("try", POSTag::VBD, "tried"),
+            ("try", POSTag::VBG, "trying"),
+            ("play", POSTag::VBD, "played"),
+            ("play", POSTag::VBZ, "plays"),
+            // Final -e
+            ("hope", POSTag::VBD, "hoped"),
+            ("hope", POSTag::VBG, "hoping"),
+            ("see", POSTag::VBG, "seeing"),
+            ("lie", POSTag::VBG, "lying"),
+            // Irregular, from the vocabulary
+            ("go", POSTag::VBD, "went"),
+            ("go", POSTag::VBN, "gone"),
+            ("go", POSTag::VBZ, "goes"),
+            ("went", POSTag::VBG, "going"),
+            ("gone", POSTag::VB, "go"),
+            ("is", POSTag::VB, "be"),
+            // Regular nouns
+            ("car", POSTag::NNS, "cars"),
+            ("cars", POSTag::NN, "car"),
+            ("box", POSTag::NNS, "boxes"),
+            ("boxes", POSTag::NN, "box"),
+            ("church", POSTag::NNS, "churches"),
+            ("knife", POSTag::NNS, "knives"),
+            // Final -y
+            ("city", POSTag::NNS, "cities"),
+            ("babies", POSTag::NN, "baby"),
+            ("day", POSTag::NNS, "days"),
+            // Irregular, from the vocabulary
+            ("mouse", POSTag::NNS, "mice"),
+            ("mice", POSTag::NN, "mouse"),
+            ("automobiles", POSTag::NN, "automobile"),
+            // Adjectives
+            ("big", POSTag::JJR, "bigger"),
+            ("happy", POSTag::JJS, "happiest"),
+            ("nice", POSTag::JJR, "nicer"),
+            ("careful", POSTag::JJR, "more careful"),
+            ("good", POSTag::JJS, "best"),
+            ("better", POSTag::JJ, "good"),
+            // Case of the original word
+            ("Stop", POSTag::VBD, "Stopped"),
+            ("MOUSE", POSTag::NNS, "MICE"),
+        ];
+
+        for (word, target, expected) in cases {
+            assert_eq!(
+                vocab.inflect(word, target).as_deref(),
+                Some(expected),
+                "{} as {}",
+                word,
+                target
+            );
+        }
+        assert_eq!(vocab.inflect("car", POSTag::RB), None);
+    }
+
+    #[test]
+    fn test_lemmatize_possessives() {
+        let vocab = fixtures::vocab();
+        let cases = [
+            ("car's", POSTag::NN, "car"),
+            ("car\u{2019}s", POSTag::NN, "car"),
+            ("cars'", POSTag::NNS, "car"),
+            ("cars\u{2019}", POSTag::NNS, "car"),
+            ("mice\u{2019}s", POSTag::NNS, "mouse"),
+            ("dog\u{2019}s", POSTag::NN, "dog"),
+            ("dogs\u{2019}", POSTag::NNS, "dog"),
+            ("Paris\u{2019}s", POSTag::NNP, "Paris"),
+        ];
+
+        for (word, pos, expected) in cases {
+            let token = Token {
+                word: word.to_string(),
+                pos,
+                ..Default::default()
+            };
+            assert_eq!(vocab.lemmatize(&token), expected, "{}", word);
+        }
+    }
+}
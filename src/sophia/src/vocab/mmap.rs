@@ -0,0 +1,799 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+//! Memory-mapped vocabulary data store.
+//!
+//! The wordlist, id2token and MWE trie are written as sorted, fixed-width record tables that are
+//! binary searched in place, so they are never deserialized in full and the OS page cache is
+//! shared between processes.  All remaining data (categories, POS tagger, etc.) is stored as a
+//! regular bincode blob within the base section.
+//!
+//! Layout (all integers little-endian):
+//!
+//! ```text
+//! header     magic (8) | format version u32 | section count u32 | (offset u64, length u64) x 4
+//! base       bincode encoded VocabDatabase, with the wordlist, id2token and MWE trie left empty
+//! wordlist   count u32 | pos table u32 | heap u32 | records (key u32, key len u32, pos u32, pos count u32) | pos table (tag u8, pad x3, id i32) | heap
+//! tokens     count u32 | heap u32 | records (id i32, offset u32, length u32) | heap of bincode encoded tokens
+//! mwe        count u32 | edges u32 | heap u32 | nodes (index i32, type u8, cap u8, pad x2, word u32, word len u32, edge u32, edge count u32) | edges (label u32, label len u32, child u32) | heap
+//! ```
+
+use super::mwe::Capitalization;
+use super::{MWEType, VocabDatabase, VocabMWE};
+use crate::error::{Error, LoadError};
+use crate::pos_tagger::POSTag;
+use crate::tokenizer::Token;
+use indexmap::IndexMap;
+use memmap2::Mmap;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::ops::Range;
+
+const MAGIC: &[u8; 8] = b"SOPHMMAP";
+const FORMAT_VERSION: u32 = 1;
+const SECTION_COUNT: usize = 4;
+const HEADER_SIZE: usize = 16 + (SECTION_COUNT * 16);
+
+const WORD_RECORD: usize = 16;
+const POS_RECORD: usize = 8;
+const TOKEN_RECORD: usize = 12;
+const NODE_RECORD: usize = 24;
+const EDGE_RECORD: usize = 12;
+
+/// A read-only, memory-mapped vocabulary data store, with the wordlist, tokens and MWE trie queried in place.
+pub struct MappedVocab {
+    mmap: Mmap,
+    base: Range<usize>,
+    wordlist: Range<usize>,
+    tokens: Range<usize>,
+    mwe: Range<usize>,
+}
+
+/// A single node of the MWE trie, read in place from a memory-mapped data store.
+#[derive(Clone, Copy)]
+pub struct MappedMWENode<'a> {
+    store: &'a MappedVocab,
+    pos: usize,
+}
+
+impl MappedVocab {
+    /// Memory-maps the data store at the given filename, and validates its header and section table.
+    pub fn open(filename: &str) -> Result<Self, Error> {
+        let file = File::open(filename)?;
+
+        // The map is read-only, and the data store is never modified in place once written.
+        let mmap = unsafe { Mmap::map(&file)? };
+        if mmap.len() < HEADER_SIZE || &mmap[..8] != MAGIC {
+            return Err(Error::Load(LoadError::Corrupt(format!(
+                "File is not a memory-mapped vocabulary data store, {}",
+                filename
+            ))));
+        }
+
+        // Check version
+        let version = u32::from_le_bytes(mmap[8..12].try_into().unwrap());
+        if version != FORMAT_VERSION {
+            return Err(Error::Load(LoadError::Corrupt(format!(
+                "Unsupported memory-mapped data store version {}, expected {}",
+                version, FORMAT_VERSION
+            ))));
+        }
+
+        // Read section table
+        let mut sections: Vec<Range<usize>> = Vec::new();
+        for x in 0..SECTION_COUNT {
+            let pos = 16 + (x * 16);
+            let offset = u64::from_le_bytes(mmap[pos..pos + 8].try_into().unwrap()) as usize;
+            let length = u64::from_le_bytes(mmap[pos + 8..pos + 16].try_into().unwrap()) as usize;
+            if offset < HEADER_SIZE || offset.saturating_add(length) > mmap.len() {
+                return Err(Error::Load(LoadError::Corrupt(format!(
+                    "Corrupt memory-mapped data store, section {} is out of bounds within {}",
+                    x, filename
+                ))));
+            }
+            sections.push(offset..offset + length);
+        }
+
+        let store = Self {
+            base: sections[0].clone(),
+            wordlist: sections[1].clone(),
+            tokens: sections[2].clone(),
+            mwe: sections[3].clone(),
+            mmap,
+        };
+
+        // Validate the record tables, so all fixed-width reads are in bounds
+        let tables = [
+            ("wordlist", &store.wordlist, 12, WORD_RECORD, vec![4, 8]),
+            ("tokens", &store.tokens, 8, TOKEN_RECORD, vec![4]),
+            ("mwe", &store.mwe, 12, NODE_RECORD, vec![4, 8]),
+        ];
+        for (name, section, header, record_size, offsets) in tables {
+            if !store.is_valid_table(section, header, record_size, &offsets) {
+                return Err(Error::Load(LoadError::Corrupt(format!(
+                    "Corrupt memory-mapped data store, {} table is out of bounds within {}",
+                    name, filename
+                ))));
+            }
+        }
+        if store.u32_at(store.mwe.start) == 0 {
+            return Err(Error::Load(LoadError::Corrupt(format!(
+                "Corrupt memory-mapped data store, MWE trie has no root node within {}",
+                filename
+            ))));
+        }
+
+        Ok(store)
+    }
+
+    /// Writes the vocabulary database to the given filename in the memory-mappable layout, sealed and optionally signed.
+    pub fn save(
+        vocab: &mut VocabDatabase,
+        filename: &str,
+        secret_key: Option<&[u8; 32]>,
+    ) -> Result<(), Error> {
+        let wordlist = Self::encode_wordlist(&vocab.words.wordlist);
+        let tokens = Self::encode_tokens(&vocab.words.id2token)?;
+        let mwe = Self::encode_mwe(&vocab.words.mwe);
+
+        // Serialize everything else, with the in-place sections temporarily taken out
+        let taken = (
+            std::mem::take(&mut vocab.words.wordlist),
+            std::mem::take(&mut vocab.words.id2token),
+            std::mem::take(&mut vocab.words.mwe),
+        );
+        let payload = bincode::serialize(&(&vocab.preprocess, &vocab.words, &vocab.categories));
+        (vocab.words.wordlist, vocab.words.id2token, vocab.words.mwe) = taken;
+
+        let payload = match payload {
+            Ok(r) => r,
+            Err(e) => {
+                return Err(Error::Save(format!(
+                    "Unable to serialize vocabulary data store, {}",
+                    e
+                )));
+            }
+        };
+
+        // Seal metadata over the payload and in-place sections
+        vocab.meta.seal(&[&payload, &wordlist, &tokens, &mwe], secret_key);
+        let mut base = match bincode::serialize(&vocab.meta) {
+            Ok(r) => r,
+            Err(e) => {
+                return Err(Error::Save(format!(
+                    "Unable to serialize vocabulary metadata, {}",
+                    e
+                )));
+            }
+        };
+        base.extend(payload);
+
+        // Header
+        let mut output: Vec<u8> = Vec::new();
+        output.extend_from_slice(MAGIC);
+        output.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        output.extend_from_slice(&(SECTION_COUNT as u32).to_le_bytes());
+
+        let mut offset = HEADER_SIZE;
+        for section in [&base, &wordlist, &tokens, &mwe] {
+            output.extend_from_slice(&(offset as u64).to_le_bytes());
+            output.extend_from_slice(&(section.len() as u64).to_le_bytes());
+            offset += section.len();
+        }
+
+        // Sections
+        for section in [base, wordlist, tokens, mwe] {
+            output.extend(section);
+        }
+        fs::write(filename, &output)?;
+
+        Ok(())
+    }
+
+    /// Returns the bincode encoded base section, containing all data not queried in place.
+    pub fn base(&self) -> &[u8] {
+        &self.mmap[self.base.clone()]
+    }
+
+    /// Returns the sealed payload, being the base section after the metadata followed by the in-place sections.
+    pub fn payload(&self, meta_len: usize) -> Vec<&[u8]> {
+        let base = self.base();
+        vec![
+            &base[meta_len.min(base.len())..],
+            &self.mmap[self.wordlist.clone()],
+            &self.mmap[self.tokens.clone()],
+            &self.mmap[self.mwe.clone()],
+        ]
+    }
+
+    /// Returns the name and length in bytes of each section queried in place.
+    pub fn section_sizes(&self) -> [(&'static str, usize); 3] {
+        [
+            ("wordlist", self.wordlist.len()),
+            ("tokens", self.tokens.len()),
+            ("mwe", self.mwe.len()),
+        ]
+    }
+
+    /// Retrieves the POS-to-ID mapping of a lowercase word from the wordlist.
+    pub fn get_pos_map(&self, word: &str) -> Option<IndexMap<POSTag, i32>> {
+        let start = self.wordlist.start;
+        let count = self.u32_at(start) as usize;
+
+        // Binary search records
+        let (mut low, mut high) = (0, count);
+        while low < high {
+            let mid = (low + high) / 2;
+            match self.word_key(mid)?.cmp(word.as_bytes()) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return self.word_pos_map(mid),
+            }
+        }
+
+        None
+    }
+
+    /// Iterates over all words within the wordlist, along with their POS-to-ID mappings.
+    pub fn words(&self) -> impl Iterator<Item = (String, IndexMap<POSTag, i32>)> + '_ {
+        let count = self.u32_at(self.wordlist.start) as usize;
+        (0..count).filter_map(|x| {
+            let word = String::from_utf8_lossy(self.word_key(x)?).to_string();
+            Some((word, self.word_pos_map(x)?))
+        })
+    }
+
+    /// Retrieves and decodes the token of the given ID, returning None if it does not exist.
+    pub fn get_token(&self, token_id: i32) -> Option<Token> {
+        let count = self.u32_at(self.tokens.start) as usize;
+
+        // Binary search records
+        let (mut low, mut high) = (0, count);
+        while low < high {
+            let mid = (low + high) / 2;
+            let record = self.tokens.start + 8 + (mid * TOKEN_RECORD);
+            match self.i32_at(record).cmp(&token_id) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return self.decode_token(mid),
+            }
+        }
+
+        None
+    }
+
+    /// Iterates over all tokens, decoding each in turn.
+    pub fn tokens(&self) -> impl Iterator<Item = (i32, Token)> + '_ {
+        let count = self.u32_at(self.tokens.start) as usize;
+        (0..count).filter_map(|x| {
+            let token_id = self.i32_at(self.tokens.start + 8 + (x * TOKEN_RECORD));
+            let token = self.decode_token(x)?;
+            Some((token_id, token))
+        })
+    }
+
+    /// Returns the root node of the MWE trie.
+    pub fn mwe_root(&self) -> MappedMWENode<'_> {
+        MappedMWENode {
+            store: self,
+            pos: self.mwe.start + 12,
+        }
+    }
+
+    /// Returns the key bytes of the wordlist record at the given position, or None if out of bounds.
+    fn word_key(&self, x: usize) -> Option<&[u8]> {
+        let start = self.wordlist.start;
+        let heap = start + self.u32_at(start + 8) as usize;
+        let record = start + 12 + (x * WORD_RECORD);
+        self.heap_slice(&self.wordlist, heap, record)
+    }
+
+    /// Decodes the POS-to-ID mapping of the wordlist record at the given position, or None if out of bounds.
+    fn word_pos_map(&self, x: usize) -> Option<IndexMap<POSTag, i32>> {
+        let start = self.wordlist.start;
+        let pos_table = start + self.u32_at(start + 4) as usize;
+        let heap = start + self.u32_at(start + 8) as usize;
+        let record = start + 12 + (x * WORD_RECORD);
+        let pos_index = self.u32_at(record + 8) as usize;
+        let pos_count = self.u32_at(record + 12) as usize;
+
+        // Ensure the entries are within the POS table
+        let pos_end = pos_index.checked_add(pos_count)?;
+        if pos_end > (heap - pos_table) / POS_RECORD {
+            return None;
+        }
+
+        let mut pos_map = IndexMap::new();
+        for y in pos_index..pos_end {
+            let entry = pos_table + (y * POS_RECORD);
+            pos_map.insert(POSTag::from_u8(self.mmap[entry]), self.i32_at(entry + 4));
+        }
+        Some(pos_map)
+    }
+
+    /// Decodes the token record at the given position.
+    fn decode_token(&self, x: usize) -> Option<Token> {
+        let start = self.tokens.start;
+        let heap = start + self.u32_at(start + 4) as usize;
+        let record = start + 8 + (x * TOKEN_RECORD);
+        bincode::deserialize(self.heap_slice(&self.tokens, heap, record + 4)?).ok()
+    }
+
+    /// Returns a slice of the section's heap, from the (offset u32, length u32) pair at the given position, or None if out of bounds.
+    fn heap_slice(&self, section: &Range<usize>, heap: usize, pos: usize) -> Option<&[u8]> {
+        let offset = heap.checked_add(self.u32_at(pos) as usize)?;
+        let end = offset.checked_add(self.u32_at(pos + 4) as usize)?;
+        self.mmap[..section.end].get(offset..end)
+    }
+
+    /// Checks the section is large enough for its header and record table, and that the table offsets within its header are
+    /// ordered and in bounds.
+    fn is_valid_table(
+        &self,
+        section: &Range<usize>,
+        header: usize,
+        record_size: usize,
+        offsets: &[usize],
+    ) -> bool {
+        if section.len() < header {
+            return false;
+        }
+
+        let count = self.u32_at(section.start) as usize;
+        let mut table_end = match count.checked_mul(record_size) {
+            Some(r) => header.saturating_add(r),
+            None => return false,
+        };
+        for pos in offsets {
+            let offset = self.u32_at(section.start + pos) as usize;
+            if offset < table_end || offset > section.len() {
+                return false;
+            }
+            table_end = offset;
+        }
+
+        table_end <= section.len()
+    }
+
+    fn u32_at(&self, pos: usize) -> u32 {
+        u32::from_le_bytes(self.mmap[pos..pos + 4].try_into().unwrap())
+    }
+
+    fn i32_at(&self, pos: usize) -> i32 {
+        i32::from_le_bytes(self.mmap[pos..pos + 4].try_into().unwrap())
+    }
+
+    /// Encodes the wordlist as sorted records, a POS table and a string heap.
+    fn encode_wordlist(wordlist: &HashMap<String, IndexMap<POSTag, i32>>) -> Vec<u8> {
+        let mut words: Vec<(&String, &IndexMap<POSTag, i32>)> = wordlist.iter().collect();
+        words.sort_by(|a, b| a.0.cmp(b.0));
+
+        let (mut records, mut pos_table, mut heap) = (Vec::new(), Vec::new(), Vec::new());
+        let mut pos_index = 0;
+        for (word, pos_map) in words.iter() {
+            records.extend_from_slice(&(heap.len() as u32).to_le_bytes());
+            records.extend_from_slice(&(word.len() as u32).to_le_bytes());
+            records.extend_from_slice(&(pos_index as u32).to_le_bytes());
+            records.extend_from_slice(&(pos_map.len() as u32).to_le_bytes());
+            heap.extend_from_slice(word.as_bytes());
+
+            for (tag, token_id) in pos_map.iter() {
+                pos_table.extend_from_slice(&[tag.to_u8(), 0, 0, 0]);
+                pos_table.extend_from_slice(&token_id.to_le_bytes());
+            }
+            pos_index += pos_map.len();
+        }
+
+        let mut output: Vec<u8> = Vec::new();
+        output.extend_from_slice(&(words.len() as u32).to_le_bytes());
+        output.extend_from_slice(&((12 + records.len()) as u32).to_le_bytes());
+        output.extend_from_slice(&((12 + records.len() + pos_table.len()) as u32).to_le_bytes());
+        output.extend(records);
+        output.extend(pos_table);
+        output.extend(heap);
+        output
+    }
+
+    /// Encodes id2token as records sorted by token ID, and a heap of bincode encoded tokens.
+    fn encode_tokens(id2token: &HashMap<i32, Token>) -> Result<Vec<u8>, Error> {
+        let mut ids: Vec<&i32> = id2token.keys().collect();
+        ids.sort();
+
+        let (mut records, mut heap) = (Vec::new(), Vec::new());
+        for token_id in ids {
+            let encoded = match bincode::serialize(&id2token[token_id]) {
+                Ok(r) => r,
+                Err(e) => {
+                    return Err(Error::Save(format!(
+                        "Unable to serialize token id# {}, {}",
+                        token_id, e
+                    )));
+                }
+            };
+
+            records.extend_from_slice(&token_id.to_le_bytes());
+            records.extend_from_slice(&(heap.len() as u32).to_le_bytes());
+            records.extend_from_slice(&(encoded.len() as u32).to_le_bytes());
+            heap.extend(encoded);
+        }
+
+        let mut output: Vec<u8> = Vec::new();
+        output.extend_from_slice(&((records.len() / TOKEN_RECORD) as u32).to_le_bytes());
+        output.extend_from_slice(&((8 + records.len()) as u32).to_le_bytes());
+        output.extend(records);
+        output.extend(heap);
+        Ok(output)
+    }
+
+    /// Encodes the MWE trie as a breadth-first node table, with each node's edges sorted by label.
+    fn encode_mwe(root: &VocabMWE) -> Vec<u8> {
+        let mut queue: Vec<&VocabMWE> = vec![root];
+        let (mut nodes, mut edges, mut heap) = (Vec::new(), Vec::new(), Vec::new());
+
+        let mut x = 0;
+        while x < queue.len() {
+            let node = queue[x];
+            let mut children: Vec<(&String, &Box<VocabMWE>)> = node.children.iter().collect();
+            children.sort_by(|a, b| a.0.cmp(b.0));
+
+            // Node record
+            let cap: u8 = match node.capitalization {
+                Capitalization::lower => 0,
+                Capitalization::upper => 1,
+                Capitalization::title => 2,
+                Capitalization::other(_) => 3,
+            };
+            let mwe_type: u8 = match node.mwe_type {
+                MWEType::standard => 0,
+                MWEType::scoring => 1,
+                MWEType::both => 2,
+            };
+            nodes.extend_from_slice(&node.index.to_le_bytes());
+            nodes.extend_from_slice(&[mwe_type, cap, 0, 0]);
+            nodes.extend_from_slice(&(heap.len() as u32).to_le_bytes());
+            nodes.extend_from_slice(&(node.orig_word.len() as u32).to_le_bytes());
+            nodes.extend_from_slice(&((edges.len() / EDGE_RECORD) as u32).to_le_bytes());
+            nodes.extend_from_slice(&(children.len() as u32).to_le_bytes());
+            heap.extend_from_slice(node.orig_word.as_bytes());
+
+            // Edges
+            for (label, child) in children {
+                edges.extend_from_slice(&(heap.len() as u32).to_le_bytes());
+                edges.extend_from_slice(&(label.len() as u32).to_le_bytes());
+                edges.extend_from_slice(&(queue.len() as u32).to_le_bytes());
+                heap.extend_from_slice(label.as_bytes());
+                queue.push(child.as_ref());
+            }
+            x += 1;
+        }
+
+        let mut output: Vec<u8> = Vec::new();
+        output.extend_from_slice(&(queue.len() as u32).to_le_bytes());
+        output.extend_from_slice(&((12 + nodes.len()) as u32).to_le_bytes());
+        output.extend_from_slice(&((12 + nodes.len() + edges.len()) as u32).to_le_bytes());
+        output.extend(nodes);
+        output.extend(edges);
+        output.extend(heap);
+        output
+    }
+}
+
+impl<'a> MappedMWENode<'a> {
+    /// Returns the token ID of the MWE ending at this node, or 0 if none.
+    pub fn index(&self) -> i32 {
+        self.store.i32_at(self.pos)
+    }
+
+    /// Returns the MWE type of this node.
+    pub fn mwe_type(&self) -> MWEType {
+        match self.store.mmap[self.pos + 4] {
+            1 => MWEType::scoring,
+            2 => MWEType::both,
+            _ => MWEType::standard,
+        }
+    }
+
+    /// Returns the capitalization style of this node.
+    pub fn capitalization(&self) -> Capitalization {
+        match self.store.mmap[self.pos + 5] {
+            1 => Capitalization::upper,
+            2 => Capitalization::title,
+            3 => Capitalization::other(self.orig_word().to_string()),
+            _ => Capitalization::lower,
+        }
+    }
+
+    /// Returns the original word of this node, as it appeared within the MWE.
+    pub fn orig_word(&self) -> &'a str {
+        let (store, start) = (self.store, self.store.mwe.start);
+        let heap = start + store.u32_at(start + 8) as usize;
+        let word = store.heap_slice(&store.mwe, heap, self.pos + 8).unwrap_or_default();
+        std::str::from_utf8(word).unwrap_or_default()
+    }
+
+    /// Retrieves the child node for the given lowercase word, if one exists.
+    pub fn get_child(&self, word: &str) -> Option<MappedMWENode<'a>> {
+        let (store, start) = (self.store, self.store.mwe.start);
+        let edge_table = start + store.u32_at(start + 4) as usize;
+        let heap = start + store.u32_at(start + 8) as usize;
+        let edges = self.edges()?;
+
+        // Binary search edges
+        let (mut low, mut high) = (edges.start, edges.end);
+        while low < high {
+            let mid = (low + high) / 2;
+            let edge = edge_table + (mid * EDGE_RECORD);
+            match store.heap_slice(&store.mwe, heap, edge)?.cmp(word.as_bytes()) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return self.child(edge),
+            }
+        }
+
+        None
+    }
+
+    /// Returns all child nodes along with their lowercase word, in sorted order.
+    pub fn children(&self) -> Vec<(&'a str, MappedMWENode<'a>)> {
+        let (store, start) = (self.store, self.store.mwe.start);
+        let edge_table = start + store.u32_at(start + 4) as usize;
+        let heap = start + store.u32_at(start + 8) as usize;
+
+        self.edges()
+            .unwrap_or_default()
+            .filter_map(|x| {
+                let edge = edge_table + (x * EDGE_RECORD);
+                let word = store.heap_slice(&store.mwe, heap, edge)?;
+                let word = std::str::from_utf8(word).unwrap_or_default();
+                Some((word, self.child(edge)?))
+            })
+            .collect()
+    }
+
+    /// Returns the range of this node's edges within the edge table, or None if out of bounds.
+    fn edges(&self) -> Option<Range<usize>> {
+        let (store, start) = (self.store, self.store.mwe.start);
+        let edge_count = (store.u32_at(start + 8) - store.u32_at(start + 4)) as usize / EDGE_RECORD;
+        let edge_index = store.u32_at(self.pos + 16) as usize;
+        let edge_end = edge_index.checked_add(store.u32_at(self.pos + 20) as usize)?;
+
+        if edge_end > edge_count {
+            return None;
+        }
+        Some(edge_index..edge_end)
+    }
+
+    /// Returns the child node the edge at the given position points to, or None if out of bounds.
+    ///
+    /// Nodes are written breadth-first, so a child always follows its parent, which keeps a corrupt trie from forming a cycle.
+    fn child(&self, edge: usize) -> Option<MappedMWENode<'a>> {
+        let (store, start) = (self.store, self.store.mwe.start);
+        let child = store.u32_at(edge + 8) as usize;
+        let parent = (self.pos - start - 12) / NODE_RECORD;
+        if child <= parent || child >= store.u32_at(start) as usize {
+            return None;
+        }
+
+        Some(MappedMWENode {
+            store,
+            pos: start + 12 + (child * NODE_RECORD),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::MWENode;
+    use crate::vocab::fixtures::{self, TempDir};
+
+    /// Saves the sample vocabulary in the memory-mappable layout, returning it along with the raw file.
+    fn save_sample(dir: &TempDir) -> (VocabDatabase, Vec<u8>) {
+        let mut vocab = fixtures::vocab();
+        vocab.save_mapped(&dir.file("en.mdat")).unwrap();
+        let data = fs::read(dir.file("en.mdat")).unwrap();
+        (vocab, data)
+    }
+
+    /// Returns the byte range of a section within the raw file.
+    fn section(data: &[u8], x: usize) -> Range<usize> {
+        let pos = 16 + (x * 16);
+        let offset = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap()) as usize;
+        let length = u64::from_le_bytes(data[pos + 8..pos + 16].try_into().unwrap()) as usize;
+        offset..offset + length
+    }
+
+    /// Asserts both MWE tries contain the same nodes, returning the number of nodes compared.
+    fn compare_mwe(owned: MWENode<'_>, mapped: MWENode<'_>) -> usize {
+        assert_eq!(owned.index(), mapped.index());
+        assert!(owned.mwe_type() == mapped.mwe_type());
+        assert_eq!(owned.format("x"), mapped.format("x"));
+
+        let mut owned_children = owned.children();
+        owned_children.sort_by(|a, b| a.0.cmp(b.0));
+        let mapped_children = mapped.children();
+        assert_eq!(
+            owned_children.iter().map(|(word, _)| *word).collect::<Vec<&str>>(),
+            mapped_children.iter().map(|(word, _)| *word).collect::<Vec<&str>>()
+        );
+
+        let mut total = 1;
+        for ((word, owned_child), (_, mapped_child)) in
+            owned_children.into_iter().zip(mapped_children)
+        {
+            assert_eq!(
+                mapped.get_child(word).map(|node| node.index()),
+                Some(mapped_child.index())
+            );
+            total += compare_mwe(owned_child, mapped_child);
+        }
+        total
+    }
+
+    /// Walks every node of the MWE trie, returning the number of nodes.
+    fn walk_mwe(node: MWENode<'_>) -> usize {
+        node.index();
+        node.format("x");
+        let children = node.children();
+        for (word, _) in children.iter() {
+            node.get_child(word);
+        }
+        1 + children.into_iter().map(|(_, child)| walk_mwe(child)).sum::<usize>()
+    }
+
+    /// Opens the raw file after writing it, along with every lookup, returning the error upon failure.
+    fn open_and_query(dir: &TempDir, data: &[u8]) -> Result<VocabDatabase, Error> {
+        fs::write(dir.file("en.mdat"), data).unwrap();
+        let vocab = VocabDatabase::load_mapped(dir.path(), "en")?;
+        for word in ["car", "run", "new", "missing"] {
+            vocab.get_pos_map(word);
+        }
+        for token_id in -1..64 {
+            vocab.get_token(token_id);
+        }
+        vocab.iter_words().count();
+        vocab.iter_tokens().count();
+        walk_mwe(vocab.mwe_root());
+        Ok(vocab)
+    }
+
+    #[test]
+    fn test_lookups() {
+        let dir = TempDir::new("mmap-lookups");
+        let (vocab, _) = save_sample(&dir);
+        let mapped = VocabDatabase::load_mapped_verified(dir.path(), "en").unwrap();
+        assert!(mapped.words.mapped.is_some());
+
+        for (word, pos_map) in vocab.words.wordlist.iter() {
+            assert_eq!(mapped.get_pos_map(word).as_ref(), Some(pos_map));
+        }
+        assert_eq!(mapped.get_pos_map("missing"), None);
+        assert_eq!(mapped.iter_words().count(), vocab.words.wordlist.len());
+
+        for token_id in vocab.words.id2token.keys() {
+            let token = vocab.get_token(*token_id).unwrap();
+            let mapped_token = mapped.get_token(*token_id).unwrap();
+            assert_eq!(format!("{:?}", token), format!("{:?}", mapped_token));
+        }
+        assert!(mapped.get_token(0).is_none());
+        assert!(mapped.get_token(i32::MAX).is_none());
+        assert_eq!(mapped.iter_tokens().count(), vocab.words.id2token.len());
+
+        let total = compare_mwe(vocab.mwe_root(), mapped.mwe_root());
+        assert_eq!(total, 6);
+        let york = mapped.mwe_root().get_child("new").and_then(|node| node.get_child("york"));
+        assert_eq!(
+            york.map(|node| node.format("new york")),
+            Some("new york".to_string())
+        );
+        assert!(mapped.mwe_root().get_child("york").is_none());
+    }
+
+    #[test]
+    fn test_truncated() {
+        let dir = TempDir::new("mmap-truncated");
+        let (_, data) = save_sample(&dir);
+
+        for len in [
+            0,
+            7,
+            8,
+            HEADER_SIZE - 1,
+            HEADER_SIZE,
+            data.len() / 2,
+            data.len() - 1,
+        ] {
+            let res = open_and_query(&dir, &data[..len]);
+            assert!(
+                matches!(res, Err(Error::Load(LoadError::Corrupt(_)))),
+                "truncated to {} bytes",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn test_corrupt_tables() {
+        let dir = TempDir::new("mmap-tables");
+        let (_, data) = save_sample(&dir);
+
+        // Record counts, table offsets and heap offsets beyond their sections
+        let wordlist = section(&data, 1);
+        let tokens = section(&data, 2);
+        let mwe = section(&data, 3);
+        for pos in [
+            wordlist.start,
+            wordlist.start + 4,
+            wordlist.start + 8,
+            tokens.start,
+            tokens.start + 4,
+            mwe.start,
+            mwe.start + 4,
+            mwe.start + 8,
+        ] {
+            let mut garbled = data.clone();
+            garbled[pos..pos + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+            let res = open_and_query(&dir, &garbled);
+            assert!(
+                matches!(res, Err(Error::Load(LoadError::Corrupt(_)))),
+                "garbled u32 at {}",
+                pos
+            );
+        }
+
+        // An MWE trie without a root node
+        let mut garbled = data.clone();
+        garbled[mwe.start..mwe.start + 4].copy_from_slice(&0u32.to_le_bytes());
+        assert!(matches!(
+            open_and_query(&dir, &garbled),
+            Err(Error::Load(LoadError::Corrupt(_)))
+        ));
+
+        // An edge pointing back to the root, which would otherwise form a cycle
+        let mut garbled = data.clone();
+        let edge_table = mwe.start
+            + u32::from_le_bytes(data[mwe.start + 4..mwe.start + 8].try_into().unwrap()) as usize;
+        garbled[edge_table + 8..edge_table + 12].copy_from_slice(&0u32.to_le_bytes());
+        let vocab = open_and_query(&dir, &garbled).unwrap();
+        assert!(walk_mwe(vocab.mwe_root()) < 6);
+
+        // Section table pointing past the end of the file
+        let mut garbled = data.clone();
+        garbled[16 + 48..16 + 56].copy_from_slice(&(data.len() as u64).to_le_bytes());
+        assert!(matches!(
+            open_and_query(&dir, &garbled),
+            Err(Error::Load(LoadError::Corrupt(_)))
+        ));
+    }
+
+    #[test]
+    fn test_garbled_records() {
+        let dir = TempDir::new("mmap-garbled");
+        let (_, data) = save_sample(&dir);
+        let in_place = section(&data, 1).start..data.len();
+
+        // Garbled records, heap offsets and edges are skipped without panicking, and fail verification
+        let mut seed: u64 = 0x5eed;
+        for _ in 0..200 {
+            let mut garbled = data.clone();
+            for _ in 0..8 {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                let pos = in_place.start + (seed >> 33) as usize % in_place.len();
+                garbled[pos] ^= (seed >> 24) as u8 | 1;
+            }
+            let _ = open_and_query(&dir, &garbled);
+
+            let res = VocabDatabase::load_mapped_verified(dir.path(), "en");
+            assert!(matches!(
+                res,
+                Err(Error::Load(
+                    LoadError::HashMismatch { .. } | LoadError::Corrupt(_)
+                ))
+            ));
+        }
+    }
+}
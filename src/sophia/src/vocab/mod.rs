@@ -4,28 +4,51 @@
 // License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
 // Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
 
-pub use self::cache::VocabCache;
+pub use self::builder::VocabBuilder;
+pub use self::cache::{DEFAULT_CACHE_CAPACITY, VocabCache};
 pub use self::category::{VocabCategory, VocabCategoryDatabase, VocabCategoryIndex};
 pub use self::database::{
-    VocabDatabase, VocabDatabaseMeta, VocabPreProcessDatabase, VocabWordDatabase,
+    VocabDatabase, VocabDatabaseMeta, VocabLoadOptions, VocabPreProcessDatabase,
+    VocabWordDatabase,
 };
+pub use self::diff::{
+    VocabDiff, VocabDiffCategoryChange, VocabDiffHashChange, VocabDiffPOSChange,
+    VocabDiffPOSModelChange,
+};
+pub use self::export::ExportFormat;
 pub use self::future_verbs::FutureVerbPhrases;
-pub use self::mwe::{Capitalization, MWEType, VocabMWE};
+pub use self::mmap::{MappedMWENode, MappedVocab};
+pub use self::mwe::{Capitalization, MWENode, MWEType, VocabMWE};
 pub use self::phrase_intents::{PhraseIntent, PhraseIntents};
 pub use self::pronoun::{Pronoun, PronounCategory, PronounGender, PronounNumber, PronounPerson};
+pub use self::search::VocabSearchFilter;
 pub use self::spell_check::{
     SpellChecker, SpellCheckerCohort, SpellCheckerCohortPOS, SpellCheckerCohortSize,
     SpellCheckerEntry,
 };
 pub use self::stats::VocabStats;
+pub use self::user_dict::{USER_TOKEN_ID_START, UserDictionary, UserWord};
+pub use self::validate::{VocabIssue, VocabIssueKind};
 
+mod builder;
 mod cache;
 mod category;
 mod database;
+mod diff;
+mod export;
 pub mod f8;
+#[cfg(test)]
+pub(crate) mod fixtures;
 mod future_verbs;
+mod hypernyms;
+mod inflection;
+mod mmap;
 pub mod mwe;
 mod phrase_intents;
 mod pronoun;
+mod search;
 mod spell_check;
 mod stats;
+mod store;
+mod user_dict;
+mod validate;
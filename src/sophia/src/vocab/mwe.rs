@@ -4,6 +4,8 @@
 // License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
 // Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
 
+use super::MappedMWENode;
+use crate::error::Error;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -17,6 +19,13 @@ pub struct VocabMWE {
     pub children: HashMap<String, Box<VocabMWE>>,
 }
 
+/// A borrowed MWE trie node, either held in memory or read in place from a memory-mapped data store.
+#[derive(Clone, Copy)]
+pub enum MWENode<'a> {
+    Owned(&'a VocabMWE),
+    Mapped(MappedMWENode<'a>),
+}
+
 /// Defines the type of a multi-word entity, which can be standard, scoring, or both.
 #[derive(Default, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub enum MWEType {
@@ -45,7 +54,7 @@ impl VocabMWE {
             index: 0,
             mwe_type,
             capitalization,
-            orig_word: String::new(),
+            orig_word: word.to_string(),
             children: HashMap::new(),
         }
     }
@@ -97,20 +106,79 @@ impl VocabMWE {
 
     /// Formats a word according to the node's capitalization style (lowercase, uppercase, title case, or original).
     pub fn format(&self, word: &str) -> String {
-        match self.capitalization {
-            Capitalization::lower => word.to_lowercase(),
-            Capitalization::upper => word.to_uppercase(),
-            Capitalization::title => format!(
-                "{}{}",
-                word.chars().next().unwrap().to_uppercase(),
-                &word[1..].to_lowercase()
-            ),
-            _ => self.orig_word.to_string(),
+        self.capitalization.apply(word, &self.orig_word)
+    }
+}
+
+impl<'a> MWENode<'a> {
+    /// Retrieves the child node for the given lowercase word, if one exists.
+    pub fn get_child(&self, word: &str) -> Option<MWENode<'a>> {
+        match self {
+            Self::Owned(node) => node.children.get(word).map(|r| Self::Owned(r.as_ref())),
+            Self::Mapped(node) => node.get_child(word).map(Self::Mapped),
+        }
+    }
+
+    /// Returns all child nodes along with their lowercase word.
+    pub fn children(&self) -> Vec<(&'a str, MWENode<'a>)> {
+        match self {
+            Self::Owned(node) => node
+                .children
+                .iter()
+                .map(|(word, child)| (word.as_str(), Self::Owned(child.as_ref())))
+                .collect(),
+            Self::Mapped(node) => node
+                .children()
+                .into_iter()
+                .map(|(word, child)| (word, Self::Mapped(child)))
+                .collect(),
+        }
+    }
+
+    /// Returns the token ID of the MWE ending at this node, or 0 if none.
+    pub fn index(&self) -> i32 {
+        match self {
+            Self::Owned(node) => node.index,
+            Self::Mapped(node) => node.index(),
+        }
+    }
+
+    /// Returns the MWE type of this node.
+    pub fn mwe_type(&self) -> MWEType {
+        match self {
+            Self::Owned(node) => node.mwe_type.clone(),
+            Self::Mapped(node) => node.mwe_type(),
+        }
+    }
+
+    /// Formats a word according to the node's capitalization style.
+    pub fn format(&self, word: &str) -> String {
+        match self {
+            Self::Owned(node) => node.format(word),
+            Self::Mapped(node) => node.capitalization().apply(word, node.orig_word()),
         }
     }
 }
 
 impl Capitalization {
+    /// Applies the capitalization style to a word, using the original word for 'other' cases.
+    pub fn apply(&self, word: &str, orig: &str) -> String {
+        match self {
+            Self::lower => word.to_lowercase(),
+            Self::upper => word.to_uppercase(),
+            Self::title => {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => {
+                        first.to_uppercase().chain(chars.as_str().to_lowercase().chars()).collect()
+                    }
+                    None => String::new(),
+                }
+            }
+            _ => orig.to_string(),
+        }
+    }
+
     /// Creates a Capitalization variant from a string value, using the original string for 'other' cases.
     pub fn from_str(value: &str, orig: &str) -> Self {
         match value {
@@ -122,3 +190,18 @@ impl Capitalization {
         }
     }
 }
+
+impl TryFrom<&str> for MWEType {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let res = match value.to_lowercase().as_str() {
+            "standard" => Self::standard,
+            "scoring" => Self::scoring,
+            "both" => Self::both,
+            _ => return Err(Error::Generic(format!("Invalid MWE type, {}", value))),
+        };
+
+        Ok(res)
+    }
+}
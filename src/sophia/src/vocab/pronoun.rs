@@ -4,6 +4,7 @@
 // License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
 // Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
 
+use crate::error::Error;
 use serde::{Deserialize, Serialize};
 
 /// Represents a pronoun with its linguistic properties, including category, gender, person, and number.
@@ -74,3 +75,74 @@ impl Pronoun {
         true
     }
 }
+
+impl TryFrom<&str> for PronounCategory {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let res = match value.to_lowercase().as_str() {
+            "none" => Self::none,
+            "personal" => Self::personal,
+            "possessive" => Self::possessive,
+            "indefinite" => Self::indefinite,
+            "reflexive" => Self::reflexive,
+            "demonstrative" => Self::demonstrative,
+            "interrogative" => Self::interrogative,
+            "relative" => Self::relative,
+            _ => {
+                return Err(Error::Generic(format!(
+                    "Invalid pronoun category, {}",
+                    value
+                )));
+            }
+        };
+
+        Ok(res)
+    }
+}
+
+impl TryFrom<&str> for PronounGender {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let res = match value.to_lowercase().as_str() {
+            "neutral" => Self::neutral,
+            "male" => Self::male,
+            "female" => Self::female,
+            _ => return Err(Error::Generic(format!("Invalid pronoun gender, {}", value))),
+        };
+
+        Ok(res)
+    }
+}
+
+impl TryFrom<&str> for PronounPerson {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let res = match value.to_lowercase().as_str() {
+            "neutral" => Self::neutral,
+            "first" => Self::first,
+            "second" => Self::second,
+            "third" => Self::third,
+            _ => return Err(Error::Generic(format!("Invalid pronoun person, {}", value))),
+        };
+
+        Ok(res)
+    }
+}
+
+impl TryFrom<&str> for PronounNumber {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let res = match value.to_lowercase().as_str() {
+            "neutral" => Self::neutral,
+            "singular" => Self::singular,
+            "plural" => Self::plural,
+            _ => return Err(Error::Generic(format!("Invalid pronoun number, {}", value))),
+        };
+
+        Ok(res)
+    }
+}
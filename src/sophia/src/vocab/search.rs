@@ -0,0 +1,395 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+use super::{MWENode, VocabDatabase};
+use crate::error::Error;
+use crate::pos_tagger::POSTag;
+use crate::tokenizer::Token;
+use regex::Regex;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Filters applied to vocabulary searches, with empty fields matching everything.
+#[derive(Clone, Default, Debug)]
+pub struct VocabSearchFilter {
+    /// Only match tokens with one of these POS tags.
+    pub pos: Vec<POSTag>,
+    /// Only match tokens assigned to this category path or any of its descendants (eg. nouns/transportation).
+    pub category: Option<String>,
+    /// Exclude multi-word entities, only matching single words.
+    pub words_only: bool,
+    /// The maximum number of results, or 0 for unlimited.
+    pub limit: usize,
+}
+
+/// A single searchable entry, being a lowercase word or MWE along with its token ID and POS tag.
+pub(crate) type SearchEntry = (String, i32, POSTag);
+
+impl VocabDatabase {
+    /// Searches for all words and MWEs starting with the prefix, ordered by frequency, for autocompletion.
+    pub fn search_prefix(&self, prefix: &str, filter: &VocabSearchFilter) -> Vec<Token> {
+        let prefix = prefix.to_lowercase();
+        let index = self.search_index();
+
+        // Binary search the sorted index
+        let start = index.partition_point(|(word, _, _)| word.as_str() < prefix.as_str());
+        let end = start + index[start..].partition_point(|(word, _, _)| word.starts_with(&prefix));
+
+        let user_entries = self.user_search_entries();
+        let matches = index[start..end]
+            .iter()
+            .chain(user_entries.iter())
+            .filter(|(word, _, _)| word.starts_with(&prefix))
+            .map(|entry| (entry, 0));
+        self.collect_matches(matches, filter).into_iter().map(|(token, _)| token).collect()
+    }
+
+    /// Searches for all words and MWEs within the maximum edit distance of the word, ordered by distance then frequency.
+    ///
+    /// Returns each matching token along with its Levenshtein distance from the word.
+    pub fn search_fuzzy(
+        &self,
+        word: &str,
+        max_distance: usize,
+        filter: &VocabSearchFilter,
+    ) -> Vec<(Token, usize)> {
+        let word: Vec<char> = word.to_lowercase().chars().collect();
+        let user_entries = self.user_search_entries();
+
+        let matches = self.search_index().iter().chain(user_entries.iter()).filter_map(|entry| {
+            let candidate: Vec<char> = entry.0.chars().collect();
+            edit_distance(&word, &candidate, max_distance).map(|distance| (entry, distance))
+        });
+        self.collect_matches(matches, filter)
+    }
+
+    /// Searches for all words and MWEs matching a glob pattern, where `*` matches any sequence and `?` any single character.
+    pub fn search_glob(&self, pattern: &str, filter: &VocabSearchFilter) -> Vec<Token> {
+        let mut regex = String::from("^");
+        for c in pattern.to_lowercase().chars() {
+            match c {
+                '*' => regex.push_str(".*"),
+                '?' => regex.push('.'),
+                _ => regex.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        regex.push('$');
+
+        // Escaped, so always valid
+        self.search_regex(&regex, filter).unwrap_or_default()
+    }
+
+    /// Searches for all words and MWEs matching a regular expression against their lowercase form, ordered by frequency.
+    pub fn search_regex(
+        &self,
+        pattern: &str,
+        filter: &VocabSearchFilter,
+    ) -> Result<Vec<Token>, Error> {
+        let regex = match Regex::new(pattern) {
+            Ok(r) => r,
+            Err(e) => {
+                return Err(Error::Generic(format!(
+                    "Invalid search pattern '{}', {}",
+                    pattern, e
+                )));
+            }
+        };
+
+        let user_entries = self.user_search_entries();
+        let matches = self
+            .search_index()
+            .iter()
+            .chain(user_entries.iter())
+            .filter(|(word, _, _)| regex.is_match(word))
+            .map(|entry| (entry, 0));
+        Ok(self.collect_matches(matches, filter).into_iter().map(|(token, _)| token).collect())
+    }
+
+    /// Returns every MWE containing the word in any position (eg. york within new york and duke of york), ordered by frequency.
+    pub fn get_mwes_containing(&self, word: &str) -> Vec<Token> {
+        let word = word.to_lowercase();
+        let mut ids: Vec<i32> = self.mwe_index().get(&word).cloned().unwrap_or_default();
+
+        // User dictionary
+        for (phrase, token_id, _) in self.user_search_entries() {
+            if phrase.contains(" ") && phrase.split(" ").any(|part| part == word) {
+                ids.push(token_id);
+            }
+        }
+
+        ids.sort();
+        ids.dedup();
+        ids.into_iter().filter_map(|id| self.get_token(id)).collect()
+    }
+
+    /// Applies the filter to matched entries, then orders them by distance and token ID up to the limit.
+    fn collect_matches<'a>(
+        &self,
+        matches: impl Iterator<Item = (&'a SearchEntry, usize)>,
+        filter: &VocabSearchFilter,
+    ) -> Vec<(Token, usize)> {
+        let categories: Option<Range<i16>> = match &filter.category {
+            Some(path) => match self.categories.path2range(path) {
+                Some(r) => Some(r),
+                None => return Vec::new(),
+            },
+            None => None,
+        };
+
+        let mut res: Vec<(Token, usize)> = Vec::new();
+        for ((word, token_id, tag), distance) in matches {
+            if (filter.words_only && word.contains(" "))
+                || (!filter.pos.is_empty() && !filter.pos.contains(tag))
+            {
+                continue;
+            }
+            let Some(token) = self.get_token(*token_id) else {
+                continue;
+            };
+
+            if let Some(range) = &categories
+                && !token.categories.iter().chain(token.ner.iter()).any(|id| range.contains(id))
+            {
+                continue;
+            }
+            res.push((token, distance));
+        }
+
+        res.sort_by_key(|(token, distance)| (*distance, token.index));
+        if filter.limit > 0 {
+            res.truncate(filter.limit);
+        }
+        res
+    }
+
+    /// Returns the sorted index of all words and MWEs within the data store, building it upon first use.
+    fn search_index(&self) -> &Vec<SearchEntry> {
+        self.words.search_index.get_or_init(|| {
+            let mut index: Vec<SearchEntry> = Vec::new();
+            for (word, pos_map) in self.iter_words() {
+                for (tag, token_id) in pos_map.iter() {
+                    index.push((word.to_string(), *token_id, *tag));
+                }
+            }
+            self.add_mwe_entries(self.mwe_root(), &mut Vec::new(), &mut index);
+
+            index.sort_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+            index
+        })
+    }
+
+    /// Returns the inverted index of each word to the IDs of all MWEs containing it, building it upon first use.
+    fn mwe_index(&self) -> &HashMap<String, Vec<i32>> {
+        self.words.mwe_index.get_or_init(|| {
+            let mut index: HashMap<String, Vec<i32>> = HashMap::new();
+            for (phrase, token_id, _) in self.search_index().iter() {
+                if !phrase.contains(" ") {
+                    continue;
+                }
+                for part in phrase.split(" ") {
+                    index.entry(part.to_string()).or_default().push(*token_id);
+                }
+            }
+            index
+        })
+    }
+
+    /// Returns the search entries of the user dictionary, which are not indexed as they may change at any time.
+    fn user_search_entries(&self) -> Vec<SearchEntry> {
+        let mut entries: Vec<SearchEntry> = Vec::new();
+        for (word, pos_map) in self.user.wordlist.iter() {
+            for (tag, token_id) in pos_map.iter() {
+                entries.push((word.to_string(), *token_id, *tag));
+            }
+        }
+        self.add_mwe_entries(
+            MWENode::Owned(&self.user.mwe),
+            &mut Vec::new(),
+            &mut entries,
+        );
+        entries
+    }
+
+    /// Walks the MWE trie, adding every complete MWE as a search entry.
+    fn add_mwe_entries<'a>(
+        &self,
+        node: MWENode<'a>,
+        path: &mut Vec<&'a str>,
+        res: &mut Vec<SearchEntry>,
+    ) {
+        let index = node.index();
+        if index > 0
+            && let Some(token) = self.get_token(index)
+        {
+            res.push((path.join(" "), index, token.pos));
+        }
+
+        for (word, child) in node.children() {
+            path.push(word);
+            self.add_mwe_entries(child, path, res);
+            path.pop();
+        }
+    }
+}
+
+/// Calculates the Levenshtein distance between two words, returning None as soon as it exceeds the maximum distance.
+fn edit_distance(a: &[char], b: &[char], max_distance: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > max_distance {
+        return None;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut current: Vec<usize> = vec![0; b.len() + 1];
+    for (x, ca) in a.iter().enumerate() {
+        current[0] = x + 1;
+        for (y, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            current[y + 1] = (prev[y] + cost).min(prev[y + 1] + 1).min(current[y] + 1);
+        }
+
+        // Every path runs through this row, so stop once it is entirely over the maximum
+        if current.iter().all(|distance| *distance > max_distance) {
+            return None;
+        }
+        std::mem::swap(&mut prev, &mut current);
+    }
+
+    if prev[b.len()] <= max_distance {
+        Some(prev[b.len()])
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::{UserWord, fixtures};
+
+    /// Returns the words of the tokens.
+    fn words(tokens: &[Token]) -> Vec<&str> {
+        tokens.iter().map(|token| token.word.as_str()).collect()
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let cases = [
+            ("kitten", "sitting", 3, Some(3)),
+            ("kitten", "sitting", 2, None),
+            ("car", "car", 0, Some(0)),
+            ("car", "cart", 1, Some(1)),
+            ("car", "cars", 0, None),
+            ("", "abc", 3, Some(3)),
+            ("abc", "", 2, None),
+            ("flaw", "lawn", 2, Some(2)),
+            ("abcdef", "ab", 3, None),
+        ];
+
+        for (a, b, max_distance, expected) in cases {
+            let a: Vec<char> = a.chars().collect();
+            let b: Vec<char> = b.chars().collect();
+            assert_eq!(edit_distance(&a, &b, max_distance), expected);
+            assert_eq!(edit_distance(&b, &a, max_distance), expected);
+        }
+    }
+
+    #[test]
+    fn test_search_fuzzy() {
+        let mut vocab = fixtures::vocab();
+        let filter = VocabSearchFilter::default();
+
+        let res: Vec<(String, usize)> = vocab
+            .search_fuzzy("Cars", 1, &filter)
+            .into_iter()
+            .map(|(token, distance)| (token.word, distance))
+            .collect();
+        assert_eq!(res, vec![("cars".to_string(), 0), ("car".to_string(), 1)]);
+
+        // Distances up to and including the cutoff, ordered by distance then token ID
+        let res: Vec<(String, usize)> = vocab
+            .search_fuzzy("cat", 2, &filter)
+            .into_iter()
+            .map(|(token, distance)| (token.word, distance))
+            .collect();
+        assert_eq!(res[0], ("car".to_string(), 1));
+        assert!(res.contains(&("cars".to_string(), 2)));
+        assert!(res.iter().all(|(_, distance)| *distance <= 2));
+        assert!(res.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        assert!(vocab.search_fuzzy("cat", 0, &filter).is_empty());
+
+        // MWEs and user dictionary words
+        let res = vocab.search_fuzzy("new yrok", 2, &filter);
+        assert_eq!(res[0].0.word, "new york");
+        vocab.add_user_word(&UserWord::new("cab", POSTag::NN)).unwrap();
+        let res = vocab.search_fuzzy("cat", 1, &filter);
+        let res: Vec<&str> = res.iter().map(|(token, _)| token.word.as_str()).collect();
+        assert_eq!(res, vec!["car", "cab"]);
+    }
+
+    #[test]
+    fn test_search_glob() {
+        let vocab = fixtures::vocab();
+        let filter = VocabSearchFilter::default();
+
+        assert_eq!(
+            words(&vocab.search_glob("car*", &filter)),
+            vec!["car", "cars"]
+        );
+        assert_eq!(words(&vocab.search_glob("car?", &filter)), vec!["cars"]);
+        assert_eq!(
+            words(&vocab.search_glob("*MOBILE", &filter)),
+            vec!["automobile"]
+        );
+        assert_eq!(words(&vocab.search_glob("?o", &filter)), vec!["go", "to"]);
+        assert_eq!(
+            words(&vocab.search_glob("new york*", &filter)),
+            vec!["new york", "new york city"]
+        );
+
+        // Other characters are matched literally
+        assert!(vocab.search_glob("c.r", &filter).is_empty());
+        assert_eq!(words(&vocab.search_glob(".", &filter)), vec!["."]);
+        assert_eq!(words(&vocab.search_glob("|num|", &filter)), vec!["|num|"]);
+        assert!(vocab.search_glob("(car)", &filter).is_empty());
+
+        let filter = VocabSearchFilter {
+            words_only: true,
+            ..Default::default()
+        };
+        assert!(vocab.search_glob("new *", &filter).is_empty());
+    }
+
+    #[test]
+    fn test_result_order() {
+        let vocab = fixtures::vocab();
+        let filter = VocabSearchFilter::default();
+
+        // Ordered by token ID, being the frequency order of the sources
+        let res = vocab.search_prefix("c", &filter);
+        assert_eq!(words(&res), vec!["car", "cars", "city"]);
+        assert!(res.windows(2).all(|pair| pair[0].index < pair[1].index));
+
+        let res = vocab.search_glob("*e", &filter);
+        assert!(res.windows(2).all(|pair| pair[0].index < pair[1].index));
+
+        // Filters are applied before the limit
+        let filter = VocabSearchFilter {
+            pos: vec![POSTag::NNS],
+            limit: 1,
+            ..Default::default()
+        };
+        assert_eq!(words(&vocab.search_glob("*s", &filter)), vec!["cars"]);
+
+        let filter = VocabSearchFilter {
+            category: Some("nouns/transportation".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            words(&vocab.search_glob("*", &filter)),
+            vec!["car", "cars", "automobile", "plane", "truck"]
+        );
+    }
+}
@@ -85,6 +85,14 @@ impl SpellChecker {
         tokens: &[Token],
         vocab: &VocabDatabase,
     ) -> Option<Token> {
+        // Check previously learned typos, resolving to the same token as scoring would
+        let word = tokens[position].word.to_lowercase();
+        if let Some(correct) = vocab.cache.get_typo(&word)
+            && let Some(token_id) = self.unambiguous_token_id(&correct, vocab)
+        {
+            return Some(vocab.from_int(token_id));
+        }
+
         // Get candidates
         let mut candidates = self.get_candidates(&tokens[position], vocab);
 
@@ -99,12 +107,28 @@ impl SpellChecker {
 
             // Sort candidates
             queue.sort_unstable_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+
+            // Only learn corrections that do not depend on the surrounding context
+            let is_unambiguous =
+                queue.iter().all(|candidate| candidate.token.index == queue[0].token.index);
+            if is_unambiguous && self.unambiguous_token_id(&queue[0].token.word, vocab).is_some() {
+                vocab.cache.add_typo(&word, &queue[0].token.word);
+            }
             return Some(queue[0].token.clone());
         }
 
         None
     }
 
+    /// Returns the token ID of a word, if it only has a single part of speech.
+    fn unambiguous_token_id(&self, word: &str, vocab: &VocabDatabase) -> Option<i32> {
+        let (_, pos_map) = vocab.lookup_word(word)?;
+        match pos_map.len() {
+            1 => pos_map.values().next().copied(),
+            _ => None,
+        }
+    }
+
     /// Get cohort based on POS tag and length
     fn get_cohorts(&self, token: &Token) -> Vec<SpellCheckerCohort> {
         // Get tags
@@ -152,7 +176,7 @@ impl SpellChecker {
 
             // Initialize variables
             let mut frequency = MAX_FREQUENCY;
-            let freq_interval = search.len() / 3;
+            let freq_interval = (search.len() / 3).max(1);
 
             // Gather candidates
             for (x, item) in search.iter().enumerate() {
@@ -6,11 +6,17 @@
 
 use super::VocabDatabase;
 use crate::pos_tagger::POSTag;
-use std::collections::HashMap;
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
 
 /// Populated with basic statistical and meta information regarding the vocabulary data store
 /// including number of words, MWEs, ambiguous words, named entities, categories, and so on.
-#[derive(Debug, Default)]
+///
+/// Also includes breakdowns by top-level category, MWE length, ambiguity, pronoun, pre-processing hash tag and POS model,
+/// plus the approximate memory use of each section, and serializes so numbers can be tracked across data store releases.
+#[derive(Debug, Default, Serialize)]
 pub struct VocabStats {
     pub singular_words: usize,
     pub ambiguous_words: usize,
@@ -24,6 +30,25 @@ pub struct VocabStats {
     pub hypernyms: usize,
     pub hyponyms: usize,
     pub pos_tags: HashMap<POSTag, usize>,
+    /// Number of tokens within each top-level category (eg. nouns/transportation), including all descendants.
+    pub categories: IndexMap<String, usize>,
+    /// Number of MWEs by their length in words.
+    pub mwe_lengths: BTreeMap<usize, usize>,
+    /// Number of words by how many POS tags they may be assigned.
+    pub ambiguity: BTreeMap<usize, usize>,
+    pub pronouns: usize,
+    /// Number of pronouns within each pronoun category (eg. personal, possessive).
+    pub pronoun_categories: HashMap<String, usize>,
+    /// Number of pre-processing hashes by their system tag (eg. |contraction|).
+    pub hash_tags: HashMap<String, usize>,
+    /// Number of per-word POS models, along with their total features and conjunctions.
+    pub pos_models: usize,
+    pub pos_model_features: usize,
+    pub pos_model_conjunctions: usize,
+    /// Number of per-word POS models able to resolve to each tag.
+    pub pos_model_tags: HashMap<POSTag, usize>,
+    /// Approximate memory use of each section in bytes, being its bincode encoded length.
+    pub memory: IndexMap<String, usize>,
 }
 
 impl VocabStats {
@@ -31,11 +56,12 @@ impl VocabStats {
         let mut stats = Self::default();
 
         // GO through wordlist
-        for (_, pos_map) in vocab.words.wordlist.iter() {
+        for (_, pos_map) in vocab.iter_words() {
             // Singular or ambiguous?
             if pos_map.len() > 1 {
                 stats.ambiguous_words += 1;
             }
+            *stats.ambiguity.entry(pos_map.len()).or_insert(0) += 1;
 
             // POS tags
             for (tag, _) in pos_map.iter() {
@@ -43,11 +69,27 @@ impl VocabStats {
             }
         }
 
+        // Top-level categories
+        let mut categories: Vec<(String, Range<i16>)> = Vec::new();
+        for name in ["nouns", "verbs", "adverbs", "adjectives", "ner"] {
+            let Some(root) = vocab.categories.root(name) else {
+                continue;
+            };
+            for child_name in root.children.keys() {
+                let path = format!("{}/{}", name, child_name);
+                if let Some(range) = vocab.categories.path2range(&path) {
+                    stats.categories.insert(path.to_string(), 0);
+                    categories.push((path, range));
+                }
+            }
+        }
+
         // Go through all tokens
-        for (_, token) in vocab.words.id2token.iter() {
+        for (_, token) in vocab.iter_tokens() {
             // MWE?
             if token.word.contains(" ") {
                 stats.mwes += 1;
+                *stats.mwe_lengths.entry(token.word.split(" ").count()).or_insert(0) += 1;
             } else {
                 stats.singular_words += 1;
             }
@@ -71,8 +113,76 @@ impl VocabStats {
             if token.is_named_entity() {
                 stats.named_entities += 1;
             }
+
+            // Categories
+            for (path, range) in categories.iter() {
+                if token.categories.iter().chain(token.ner.iter()).any(|id| range.contains(id)) {
+                    *stats.categories.entry(path.to_string()).or_insert(0) += 1;
+                }
+            }
+
+            // Pronouns
+            if let Some(pronoun) = &token.pronoun {
+                stats.pronouns += 1;
+                let category = format!("{:?}", pronoun.category);
+                *stats.pronoun_categories.entry(category).or_insert(0) += 1;
+            }
+        }
+
+        // Pre-processing hashes
+        for (tag, _) in vocab.preprocess.hashes.values() {
+            *stats.hash_tags.entry(tag.to_string()).or_insert(0) += 1;
+        }
+
+        // POS models
+        for model in vocab.words.pos_tagger.words.values() {
+            stats.pos_models += 1;
+            stats.pos_model_features += model.features.len();
+            stats.pos_model_conjunctions +=
+                model.conjunctions.values().map(|c| c.len()).sum::<usize>();
+            for tag in model.target_tags.iter() {
+                *stats.pos_model_tags.entry(*tag).or_insert(0) += 1;
+            }
         }
 
+        stats.memory = Self::memory(vocab);
         stats
     }
+
+    /// Returns the approximate memory use of each section in bytes, being its bincode encoded length.
+    fn memory(vocab: &VocabDatabase) -> IndexMap<String, usize> {
+        let words = &vocab.words;
+        let preprocess = &vocab.preprocess;
+
+        let mut memory: IndexMap<String, usize> = IndexMap::new();
+        for (name, bytes) in [
+            ("hashes", encoded_size(&preprocess.hashes)),
+            ("spellchecker", encoded_size(&preprocess.spellchecker)),
+            ("wordlist", encoded_size(&words.wordlist)),
+            ("tokens", encoded_size(&words.id2token)),
+            ("mwe", encoded_size(&words.mwe)),
+            ("pos_tagger", encoded_size(&words.pos_tagger)),
+            ("capitalization", encoded_size(&words.capitalization)),
+            ("future_verbs", encoded_size(&words.future_verbs)),
+            ("phrase_intents", encoded_size(&words.phrase_intents)),
+            ("plural", encoded_size(&words.plural)),
+            ("categories", encoded_size(&vocab.categories)),
+        ] {
+            memory.insert(name.to_string(), bytes);
+        }
+
+        // Sections queried in place from a memory-mapped data store
+        if let Some(mapped) = &words.mapped {
+            for (name, bytes) in mapped.section_sizes() {
+                memory.insert(name.to_string(), bytes);
+            }
+        }
+
+        memory
+    }
+}
+
+/// Returns the bincode encoded length of a value, or 0 if it can not be encoded.
+fn encoded_size<T: Serialize>(value: &T) -> usize {
+    bincode::serialized_size(value).map(|r| r as usize).unwrap_or(0)
 }
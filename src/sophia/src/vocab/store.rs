@@ -0,0 +1,477 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+//! Versioned, self-describing vocabulary data store.
+//!
+//! The header identifies the file, the format version and the version of Sophia that wrote it,
+//! followed by the metadata and a section table giving the length and SHA256 hash of every section.
+//! Sections are read in table order with unknown sections skipped, and each is verified against its
+//! own hash so corruption or version skew is reported against the section it occurred in.  Data
+//! stores written before the header was introduced are read through the legacy layout.
+//!
+//! Layout (all integers little-endian, optionally compressed as a single zstd stream):
+//!
+//! ```text
+//! header     magic (8) | format version u32 | header length u32 | bincode encoded DataStoreHeader
+//! sections   bincode encoded preprocess, words and categories, in section table order
+//! legacy     bincode encoded metadata | bincode encoded (preprocess, words, categories)
+//! ```
+
+use super::{
+    UserDictionary, VocabCache, VocabCategoryDatabase, VocabDatabase, VocabDatabaseMeta,
+    VocabPreProcessDatabase, VocabWordDatabase,
+};
+use crate::error::{Error, LoadError};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{self, Read};
+
+const MAGIC: &[u8; 8] = b"SOPHVDAT";
+const FORMAT_VERSION: u32 = 1;
+
+/// The header of a data store, describing the version of Sophia that wrote it and each of its sections.
+#[derive(Serialize, Deserialize)]
+struct DataStoreHeader {
+    writer_version: String,
+    meta: VocabDatabaseMeta,
+    sections: Vec<DataStoreSection>,
+}
+
+/// An entry within the section table, giving the name, length and hex encoded SHA256 hash of a section.
+#[derive(Serialize, Deserialize)]
+struct DataStoreSection {
+    name: String,
+    length: u64,
+    sha256_hash: String,
+}
+
+/// A reader which hashes all bytes read through it, allowing the payload to be verified while it is deserialized.
+struct HashingReader<R: Read> {
+    inner: R,
+    hasher: Sha256,
+}
+
+impl VocabDatabase {
+    /// Encodes the data store with its header and section table, sealing the metadata over all sections.
+    pub(super) fn encode(&mut self, secret_key: Option<&[u8; 32]>) -> Result<Vec<u8>, Error> {
+        let sections = [
+            ("preprocess", serialize(&self.preprocess, "preprocess")?),
+            ("words", serialize(&self.words, "words")?),
+            ("categories", serialize(&self.categories, "categories")?),
+        ];
+        let payload: Vec<&[u8]> = sections.iter().map(|(_, data)| &data[..]).collect();
+        self.meta.seal(&payload, secret_key);
+
+        // Header
+        let header = DataStoreHeader {
+            writer_version: env!("CARGO_PKG_VERSION").to_string(),
+            meta: self.meta.clone(),
+            sections: sections
+                .iter()
+                .map(|(name, data)| DataStoreSection {
+                    name: name.to_string(),
+                    length: data.len() as u64,
+                    sha256_hash: hex::encode(Sha256::digest(data)),
+                })
+                .collect(),
+        };
+        let header = serialize(&header, "header")?;
+
+        let mut output: Vec<u8> = Vec::new();
+        output.extend_from_slice(MAGIC);
+        output.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        output.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        output.extend(header);
+        for (_, data) in sections {
+            output.extend(data);
+        }
+
+        Ok(output)
+    }
+
+    /// Reads a data store from the reader, detecting the legacy layout by the absence of the magic bytes.
+    pub(super) fn read_from<R: Read>(
+        mut reader: R,
+        allow_unsealed: bool,
+    ) -> Result<VocabDatabase, Error> {
+        let mut magic = [0u8; 8];
+        let len = read_up_to(&mut reader, &mut magic)?;
+        if &magic != MAGIC {
+            return Self::read_legacy((&magic[..len]).chain(reader), allow_unsealed);
+        }
+
+        // Check format version
+        let mut buf = [0u8; 8];
+        reader.read_exact(&mut buf)?;
+        let format_version = u32::from_le_bytes(buf[..4].try_into().unwrap());
+        let header_len = u32::from_le_bytes(buf[4..].try_into().unwrap()) as u64;
+        if format_version != FORMAT_VERSION {
+            return Err(Error::Load(LoadError::FormatMismatch {
+                expected: FORMAT_VERSION,
+                found: format_version,
+            }));
+        }
+
+        let header: DataStoreHeader =
+            match bincode::deserialize_from((&mut reader).take(header_len)) {
+                Ok(r) => r,
+                Err(e) => {
+                    return Err(Error::Load(LoadError::Corrupt(format!(
+                        "Unable to read the vocabulary data store header, {}",
+                        e
+                    ))));
+                }
+            };
+        header.meta.verify_version()?;
+        header.meta.verify_sealed(allow_unsealed)?;
+
+        // Read sections, hashing all bytes read
+        let mut payload = HashingReader::new(reader);
+        let (mut preprocess, mut words, mut categories) = (None, None, None);
+        for section in header.sections.iter() {
+            let mut section_reader = HashingReader::new((&mut payload).take(section.length));
+            match section.name.as_str() {
+                "preprocess" => {
+                    preprocess = Some(header.read_section(&mut section_reader, section)?)
+                }
+                "words" => words = Some(header.read_section(&mut section_reader, section)?),
+                "categories" => {
+                    categories = Some(header.read_section(&mut section_reader, section)?)
+                }
+                _ => header.read_section::<_, ()>(&mut section_reader, section).map(|_| ())?,
+            }
+        }
+        io::copy(&mut payload, &mut io::sink())?;
+        header.meta.verify_hash(&payload.finalize())?;
+
+        Ok(VocabDatabase {
+            preprocess: header.require(preprocess, "preprocess")?,
+            words: header.require(words, "words")?,
+            categories: header.require(categories, "categories")?,
+            meta: header.meta,
+            cache: VocabCache::default(),
+            user: UserDictionary::default(),
+        })
+    }
+
+    /// Reads a data store written before the header was introduced, being the metadata followed by the payload.
+    fn read_legacy<R: Read>(mut reader: R, allow_unsealed: bool) -> Result<VocabDatabase, Error> {
+        let meta: VocabDatabaseMeta = match bincode::deserialize_from(&mut reader) {
+            Ok(r) => r,
+            Err(e) => {
+                return Err(Error::Load(LoadError::Corrupt(format!(
+                    "File is not a vocabulary data store, unable to read its metadata, {}",
+                    e
+                ))));
+            }
+        };
+        meta.verify_version()?;
+        meta.verify_sealed(allow_unsealed)?;
+
+        // Deserialize payload, hashing all bytes read
+        let mut hashing = HashingReader::new(reader);
+        let payload: Result<
+            (
+                VocabPreProcessDatabase,
+                VocabWordDatabase,
+                VocabCategoryDatabase,
+            ),
+            _,
+        > = bincode::deserialize_from(&mut hashing);
+
+        // Include any remaining bytes, so a tampered file reports a hash mismatch over a parse error
+        io::copy(&mut hashing, &mut io::sink())?;
+        meta.verify_hash(&hashing.finalize())?;
+
+        let (preprocess, words, categories) = match payload {
+            Ok(r) => r,
+            Err(e) => {
+                return Err(Error::Load(LoadError::Incompatible {
+                    section: "payload".to_string(),
+                    writer_version: String::new(),
+                    reason: e.to_string(),
+                }));
+            }
+        };
+
+        Ok(VocabDatabase {
+            meta,
+            preprocess,
+            words,
+            categories,
+            cache: VocabCache::default(),
+            user: UserDictionary::default(),
+        })
+    }
+}
+
+impl DataStoreHeader {
+    /// Deserializes a section, verifying its hash before reporting any deserialization error as version skew.
+    fn read_section<R: Read, T: DeserializeOwned>(
+        &self,
+        reader: &mut HashingReader<R>,
+        section: &DataStoreSection,
+    ) -> Result<T, Error> {
+        let res = bincode::deserialize_from(&mut *reader);
+        io::copy(reader, &mut io::sink())?;
+
+        let hash = std::mem::replace(&mut reader.hasher, Sha256::new());
+        let hash = hex::encode(hash.finalize());
+        if hash != section.sha256_hash {
+            return Err(Error::Load(LoadError::SectionHashMismatch {
+                section: section.name.to_string(),
+                expected: section.sha256_hash.to_string(),
+                found: hash,
+            }));
+        }
+
+        match res {
+            Ok(r) => Ok(r),
+            Err(e) => Err(Error::Load(LoadError::Incompatible {
+                section: section.name.to_string(),
+                writer_version: self.writer_version.to_string(),
+                reason: e.to_string(),
+            })),
+        }
+    }
+
+    /// Returns a section that must be present within the data store.
+    fn require<T>(&self, section: Option<T>, name: &str) -> Result<T, Error> {
+        match section {
+            Some(r) => Ok(r),
+            None => Err(Error::Load(LoadError::Corrupt(format!(
+                "Vocabulary data store written by Sophia v{} is missing the '{}' section",
+                self.writer_version, name
+            )))),
+        }
+    }
+}
+
+impl<R: Read> HashingReader<R> {
+    /// Wraps the reader, hashing all bytes read through it.
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Returns the hex encoded SHA256 hash of all bytes read.
+    fn finalize(self) -> String {
+        hex::encode(self.hasher.finalize())
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.hasher.update(&buf[..len]);
+        Ok(len)
+    }
+}
+
+/// Serializes a section of the data store.
+fn serialize<T: Serialize>(value: &T, name: &str) -> Result<Vec<u8>, Error> {
+    match bincode::serialize(value) {
+        Ok(r) => Ok(r),
+        Err(e) => Err(Error::Save(format!(
+            "Unable to serialize the '{}' section of the vocabulary data store, {}",
+            name, e
+        ))),
+    }
+}
+
+/// Reads until the buffer is full or the reader is exhausted, returning the number of bytes read.
+fn read_up_to<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match reader.read(&mut buf[len..])? {
+            0 => break,
+            n => len += n,
+        }
+    }
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::fixtures::{self, TempDir};
+    use std::fs;
+
+    /// Asserts the loaded vocabulary contains the same words, tokens and categories as the original.
+    fn assert_same(vocab: &VocabDatabase, loaded: &VocabDatabase) {
+        assert_eq!(vocab.words.wordlist, loaded.words.wordlist);
+        assert_eq!(vocab.words.id2token.len(), loaded.words.id2token.len());
+        for (token_id, token) in vocab.words.id2token.iter() {
+            assert_eq!(
+                format!("{:?}", token),
+                format!("{:?}", loaded.words.id2token[token_id])
+            );
+        }
+        assert_eq!(vocab.words.plural, loaded.words.plural);
+        assert_eq!(vocab.categories.nodes.len(), loaded.categories.nodes.len());
+        assert_eq!(vocab.preprocess.hashes, loaded.preprocess.hashes);
+        assert_eq!(vocab.meta.sha256_hash(), loaded.meta.sha256_hash());
+    }
+
+    /// Encodes the sections in the data store layout, sealing the metadata over them.
+    fn encode_sections(vocab: &mut VocabDatabase, sections: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let payload: Vec<&[u8]> = sections.iter().map(|(_, data)| &data[..]).collect();
+        vocab.meta.seal(&payload, None);
+
+        let header = DataStoreHeader {
+            writer_version: "0.0.0".to_string(),
+            meta: vocab.meta.clone(),
+            sections: sections
+                .iter()
+                .map(|(name, data)| DataStoreSection {
+                    name: name.to_string(),
+                    length: data.len() as u64,
+                    sha256_hash: hex::encode(Sha256::digest(data)),
+                })
+                .collect(),
+        };
+        let header = bincode::serialize(&header).unwrap();
+
+        let mut output: Vec<u8> = Vec::new();
+        output.extend_from_slice(MAGIC);
+        output.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        output.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        output.extend(header);
+        for (_, data) in sections {
+            output.extend_from_slice(data);
+        }
+        output
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = TempDir::new("store-round-trip");
+        let mut vocab = fixtures::vocab();
+        vocab.save(&dir.file("en.dat")).unwrap();
+        assert!(fs::read(dir.file("en.dat")).unwrap().starts_with(MAGIC));
+        assert!(!vocab.meta.sha256_hash().is_empty());
+
+        let loaded = VocabDatabase::load(dir.path(), "en").unwrap();
+        assert_same(&vocab, &loaded);
+
+        // Compressed, detected by the zstd magic
+        fs::remove_file(dir.file("en.dat")).unwrap();
+        vocab.save_compressed(&dir.file("en.dat.zst"), 3).unwrap();
+        let loaded = VocabDatabase::load(dir.path(), "en").unwrap();
+        assert_same(&vocab, &loaded);
+    }
+
+    #[test]
+    fn test_flipped_byte() {
+        let dir = TempDir::new("store-flipped");
+        let mut vocab = fixtures::vocab();
+        let data = vocab.encode(None).unwrap();
+
+        // A flipped byte within each section is reported against that section
+        let header_len = u32::from_le_bytes(data[12..16].try_into().unwrap()) as usize;
+        let header: DataStoreHeader = bincode::deserialize(&data[16..16 + header_len]).unwrap();
+        let mut offset = 16 + header_len;
+        for section in header.sections.iter() {
+            let mut flipped = data.clone();
+            flipped[offset + (section.length as usize / 2)] ^= 0x01;
+            fs::write(dir.file("en.dat"), &flipped).unwrap();
+
+            match VocabDatabase::load(dir.path(), "en") {
+                Err(Error::Load(LoadError::SectionHashMismatch { section: name, .. })) => {
+                    assert_eq!(name, section.name)
+                }
+                _ => panic!(
+                    "expected a hash mismatch within the '{}' section",
+                    section.name
+                ),
+            }
+            offset += section.length as usize;
+        }
+
+        // Trailing bytes are included within the payload hash
+        let mut appended = data.clone();
+        appended.push(0);
+        fs::write(dir.file("en.dat"), &appended).unwrap();
+        assert!(matches!(
+            VocabDatabase::load(dir.path(), "en"),
+            Err(Error::Load(LoadError::HashMismatch { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_format_version() {
+        let dir = TempDir::new("store-format");
+        let mut data = fixtures::vocab().encode(None).unwrap();
+        data[8..12].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        fs::write(dir.file("en.dat"), &data).unwrap();
+
+        match VocabDatabase::load(dir.path(), "en") {
+            Err(Error::Load(LoadError::FormatMismatch { expected, found })) => {
+                assert_eq!((expected, found), (FORMAT_VERSION, FORMAT_VERSION + 1))
+            }
+            _ => panic!("expected a format version mismatch"),
+        }
+    }
+
+    #[test]
+    fn test_unknown_section() {
+        let dir = TempDir::new("store-unknown");
+        let mut vocab = fixtures::vocab();
+        let sections = [
+            ("preprocess", bincode::serialize(&vocab.preprocess).unwrap()),
+            ("synonyms_v2", vec![1, 2, 3, 4]),
+            ("words", bincode::serialize(&vocab.words).unwrap()),
+            ("categories", bincode::serialize(&vocab.categories).unwrap()),
+        ];
+        let data = encode_sections(&mut vocab, &sections);
+        fs::write(dir.file("en.dat"), &data).unwrap();
+
+        let loaded = VocabDatabase::load(dir.path(), "en").unwrap();
+        assert_same(&vocab, &loaded);
+
+        // A required section that is missing
+        let data = encode_sections(&mut vocab, &sections[..3]);
+        fs::write(dir.file("en.dat"), &data).unwrap();
+        assert!(matches!(
+            VocabDatabase::load(dir.path(), "en"),
+            Err(Error::Load(LoadError::Corrupt(_)))
+        ));
+    }
+
+    #[test]
+    fn test_legacy() {
+        let dir = TempDir::new("store-legacy");
+        let mut vocab = fixtures::vocab();
+
+        // Metadata followed by the payload, without the header
+        let payload =
+            bincode::serialize(&(&vocab.preprocess, &vocab.words, &vocab.categories)).unwrap();
+        vocab.meta.seal(&[&payload], None);
+        let mut data = bincode::serialize(&vocab.meta).unwrap();
+        data.extend_from_slice(&payload);
+        fs::write(dir.file("en.dat"), &data).unwrap();
+
+        let mut loaded = VocabDatabase::load(dir.path(), "en").unwrap();
+        assert_same(&vocab, &loaded);
+
+        // Written with the header when next saved
+        loaded.save(&dir.file("en.dat")).unwrap();
+        assert!(fs::read(dir.file("en.dat")).unwrap().starts_with(MAGIC));
+        assert_same(&loaded, &VocabDatabase::load(dir.path(), "en").unwrap());
+
+        // A flipped byte within the legacy payload
+        let last = data.len() - 1;
+        data[last] ^= 0x01;
+        fs::write(dir.file("en.dat"), &data).unwrap();
+        assert!(matches!(
+            VocabDatabase::load(dir.path(), "en"),
+            Err(Error::Load(LoadError::HashMismatch { .. }))
+        ));
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+use super::mwe::Capitalization;
+use super::{MWENode, MWEType, VocabMWE};
+use crate::error::{Error, LoadError};
+use crate::pos_tagger::POSTag;
+use crate::tokenizer::Token;
+use indexmap::IndexMap;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// The first token ID assigned to user dictionary entries, keeping them clear of vocabulary token IDs.
+pub const USER_TOKEN_ID_START: i32 = 1_000_000_000;
+
+/// A runtime overlay of user defined words and MWEs, consulted before the vocabulary database without modifying it.
+pub struct UserDictionary {
+    pub wordlist: HashMap<String, IndexMap<POSTag, i32>>,
+    pub id2token: HashMap<i32, Token>,
+    pub mwe: VocabMWE,
+    pub capitalization: HashMap<i32, Capitalization>,
+    category_index: HashMap<i16, Vec<i32>>,
+    next_id: i32,
+}
+
+/// A single user dictionary entry, with MWEs being any word containing a space.
+#[derive(Clone, Deserialize)]
+pub struct UserWord {
+    pub word: String,
+    pub pos: POSTag,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub ner: Vec<String>,
+    #[serde(default)]
+    pub mwe_type: MWEType,
+}
+
+/// The contents of a TOML user dictionary file.
+#[derive(Deserialize)]
+struct UserDictionaryFile {
+    #[serde(default)]
+    words: Vec<UserWord>,
+}
+
+impl UserDictionary {
+    /// Creates a new, empty user dictionary.
+    pub fn new() -> Self {
+        Self {
+            wordlist: HashMap::new(),
+            id2token: HashMap::new(),
+            mwe: VocabMWE::default(),
+            capitalization: HashMap::new(),
+            category_index: HashMap::new(),
+            next_id: USER_TOKEN_ID_START,
+        }
+    }
+
+    /// Parses the contents of a user dictionary file, either TOML or tab delimited text.
+    ///
+    /// Text files contain one entry per line as `word  POS  [key=value ...]` with supported keys `categories`, `ner` and `type`,
+    /// while TOML files contain a `[[words]]` table per entry with the same fields as `UserWord`.
+    pub fn parse(contents: &str, is_toml: bool) -> Result<Vec<UserWord>, Error> {
+        if is_toml {
+            return match toml::from_str::<UserDictionaryFile>(contents) {
+                Ok(r) => Ok(r.words),
+                Err(e) => Err(Error::Load(LoadError::Parse(format!(
+                    "Unable to parse user dictionary, {}",
+                    e
+                )))),
+            };
+        }
+
+        let mut entries: Vec<UserWord> = Vec::new();
+        for (x, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() || line.trim_start().starts_with("#") {
+                continue;
+            }
+            let parts: Vec<&str> = line.split("\t").map(|part| part.trim()).collect();
+
+            // Word and POS tag
+            let pos = POSTag::from_str(parts.get(1).unwrap_or(&""));
+            if parts.len() < 2 || parts[0].is_empty() || pos.to_str() != parts[1].to_uppercase() {
+                return Err(Error::Load(LoadError::Parse(format!(
+                    "Expected a word and valid POS tag on line {} of user dictionary",
+                    x + 1
+                ))));
+            }
+            let mut entry = UserWord::new(parts[0], pos);
+
+            // Attributes
+            for part in parts[2..].iter().filter(|part| !part.is_empty()) {
+                let (key, value) = part.split_once("=").unwrap_or((part, ""));
+                let values: Vec<String> = value
+                    .split(",")
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty())
+                    .collect();
+
+                match key.trim().to_lowercase().as_str() {
+                    "categories" => entry.categories = values,
+                    "ner" => entry.ner = values,
+                    "type" => {
+                        entry.mwe_type = match MWEType::try_from(value.trim()) {
+                            Ok(r) => r,
+                            Err(_) => {
+                                return Err(Error::Load(LoadError::Parse(format!(
+                                    "Invalid MWE type '{}' on line {} of user dictionary",
+                                    value,
+                                    x + 1
+                                ))));
+                            }
+                        }
+                    }
+                    _ => {
+                        return Err(Error::Load(LoadError::Parse(format!(
+                            "Invalid attribute '{}' on line {} of user dictionary",
+                            part,
+                            x + 1
+                        ))));
+                    }
+                }
+            }
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+
+    /// Adds an entry with resolved category and NER IDs, returning its token ID.  Existing entries with the same word and POS tag are replaced.
+    pub fn add(&mut self, entry: &UserWord, categories: Vec<i16>, ner: Vec<i16>) -> i32 {
+        let key = entry.word.to_lowercase();
+
+        // Get token ID
+        let existing = if entry.word.contains(" ") {
+            Some(self.mwe.get(&key)).filter(|index| *index > 0)
+        } else {
+            self.wordlist.get(&key).and_then(|pos_map| pos_map.get(&entry.pos).copied())
+        };
+        let index = match existing {
+            Some(r) => r,
+            None => {
+                self.next_id += 1;
+                self.next_id - 1
+            }
+        };
+
+        // Add to MWE trie or wordlist
+        if entry.word.contains(" ") {
+            self.mwe.insert(&entry.word, index, entry.mwe_type.clone());
+        } else {
+            self.wordlist.entry(key).or_default().insert(entry.pos, index);
+            match VocabMWE::classify_capitalization(&entry.word) {
+                Capitalization::lower => self.capitalization.remove(&index),
+                capitalization => self.capitalization.insert(index, capitalization),
+            };
+        }
+
+        // Update category index, removing the categories of any replaced entry
+        if let Some(replaced) = self.id2token.get(&index) {
+            for category_id in replaced.categories.iter().chain(replaced.ner.iter()) {
+                if let Some(ids) = self.category_index.get_mut(category_id) {
+                    ids.retain(|id| *id != index);
+                }
+            }
+        }
+        for category_id in categories.iter().chain(ner.iter()) {
+            let ids = self.category_index.entry(*category_id).or_default();
+            if !ids.contains(&index) {
+                ids.push(index);
+            }
+        }
+
+        let token = Token {
+            word: entry.word.to_string(),
+            stem: index,
+            pos: entry.pos,
+            categories,
+            ner,
+            ..Default::default()
+        };
+        self.id2token.insert(index, token);
+
+        index
+    }
+
+    /// Returns the token IDs of all entries assigned to a category or NER label.
+    pub fn get_category_words(&self, category_id: i16) -> &[i32] {
+        match self.category_index.get(&category_id) {
+            Some(ids) => ids,
+            None => &[],
+        }
+    }
+
+    /// Checks whether the token ID belongs to the user dictionary.
+    pub fn contains_id(&self, token_id: i32) -> bool {
+        token_id >= USER_TOKEN_ID_START
+    }
+
+    /// Formats a word according to the capitalization of the user dictionary entry, or None if it has no capitalization.
+    pub fn format(&self, token_id: i32, word: &str) -> Option<String> {
+        let capitalization = self.capitalization.get(&token_id)?;
+        let orig = &self.id2token.get(&token_id)?.word;
+        Some(capitalization.apply(word, orig))
+    }
+
+    /// Returns the root node of the user MWE trie.
+    pub fn mwe_root(&self) -> MWENode<'_> {
+        MWENode::Owned(&self.mwe)
+    }
+
+    /// Returns the number of entries within the user dictionary.
+    pub fn len(&self) -> usize {
+        self.id2token.len()
+    }
+
+    /// Checks whether the user dictionary is empty.
+    pub fn is_empty(&self) -> bool {
+        self.id2token.is_empty()
+    }
+}
+
+impl Default for UserDictionary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UserWord {
+    /// Creates a new user dictionary entry for the word and POS tag, with no categories.
+    pub fn new(word: &str, pos: POSTag) -> Self {
+        Self {
+            word: word.to_string(),
+            pos,
+            categories: Vec::new(),
+            ner: Vec::new(),
+            mwe_type: MWEType::standard,
+        }
+    }
+}
@@ -0,0 +1,273 @@
+// Copyright 2025 Aquila Labs of Alberta, Canada <matt@cicero.sh>
+// Licensed under the PolyForm Noncommercial License 1.0.0
+// Commercial use requires a separate license: https://cicero.sh/sophia/
+// License text: https://polyformproject.org/licenses/noncommercial/1.0.0/
+// Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
+
+use super::{MWENode, PhraseIntents, VocabCategoryIndex, VocabDatabase};
+use std::collections::HashSet;
+use std::fmt;
+
+/// A single issue found while validating a vocabulary data store, along with the location it was found at.
+#[derive(Debug, Clone)]
+pub struct VocabIssue {
+    pub kind: VocabIssueKind,
+    pub location: String,
+    pub message: String,
+}
+
+/// The type of issue found while validating a vocabulary data store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VocabIssueKind {
+    empty_pos_map,
+    dangling_token,
+    dangling_mwe,
+    dangling_category,
+    dangling_plural,
+    dangling_reference,
+    pos_mismatch,
+}
+
+/// Tracks the token IDs within the data store, and collects issues as each structure is walked.
+struct Validator<'a> {
+    vocab: &'a VocabDatabase,
+    token_ids: HashSet<i32>,
+    issues: Vec<VocabIssue>,
+}
+
+impl VocabDatabase {
+    /// Walks every structure within the data store, returning all issues found along with their locations.
+    ///
+    /// Checks for empty POS maps and dangling token IDs within the wordlist, MWE trie nodes pointing to missing tokens,
+    /// category IDs missing from the category tree, plural entries referencing missing tokens, and dangling token
+    /// references within tokens, word lists, POS models, capitalization and phrase intents.  User dictionary entries are not validated.
+    pub fn validate(&self) -> Vec<VocabIssue> {
+        let mut validator = Validator {
+            vocab: self,
+            token_ids: self.iter_tokens().map(|(id, _)| id).collect(),
+            issues: Vec::new(),
+        };
+
+        validator.check_wordlist();
+        validator.check_tokens();
+        validator.check_mwe(self.mwe_root(), &mut Vec::new());
+        validator.check_plurals();
+        validator.check_categories();
+        validator.check_preprocess();
+        validator.check_words();
+
+        validator.issues.sort_by(|a, b| a.location.cmp(&b.location));
+        validator.issues
+    }
+}
+
+impl Validator<'_> {
+    /// Checks every word within the wordlist has at least one POS tag, with each pointing to a token of the same tag.
+    fn check_wordlist(&mut self) {
+        let vocab = self.vocab;
+        for (word, pos_map) in vocab.iter_words() {
+            let location = format!("wordlist[{}]", word);
+            if pos_map.is_empty() {
+                self.add(
+                    VocabIssueKind::empty_pos_map,
+                    &location,
+                    "word has no POS tags",
+                );
+                continue;
+            }
+
+            for (tag, token_id) in pos_map.iter() {
+                let location = format!("{}/{}", location, tag);
+                let Some(token) = vocab.get_token(*token_id) else {
+                    self.add(
+                        VocabIssueKind::dangling_token,
+                        &location,
+                        &format!("token ID {} does not exist", token_id),
+                    );
+                    continue;
+                };
+
+                if token.pos != *tag {
+                    self.add(
+                        VocabIssueKind::pos_mismatch,
+                        &location,
+                        &format!("token ID {} is tagged as {}", token_id, token.pos),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Checks all category IDs and token references within every token.
+    fn check_tokens(&mut self) {
+        let vocab = self.vocab;
+        for (token_id, token) in vocab.iter_tokens() {
+            let location = format!("id2token[{}]", token_id);
+
+            for category_id in token.categories.iter().chain(token.ner.iter()) {
+                if !vocab.categories.nodes.contains_key(category_id) {
+                    self.add(
+                        VocabIssueKind::dangling_category,
+                        &location,
+                        &format!("category ID {} does not exist", category_id),
+                    );
+                }
+            }
+
+            if token.stem != 0 {
+                self.check_id(&format!("{}.stem", location), token.stem);
+            }
+            for (key, ids) in [
+                ("potential_stem", &token.potential_stem),
+                ("synonyms", &token.synonyms),
+                ("hypernyms", &token.hypernyms),
+                ("hyponyms", &token.hyponyms),
+            ] {
+                for id in ids.iter() {
+                    self.check_id(&format!("{}.{}", location, key), *id);
+                }
+            }
+        }
+    }
+
+    /// Checks every node of the MWE trie that completes an MWE points to an existing token.
+    fn check_mwe(&mut self, node: MWENode<'_>, path: &mut Vec<String>) {
+        let index = node.index();
+        if index > 0 && !self.token_ids.contains(&index) {
+            self.add(
+                VocabIssueKind::dangling_mwe,
+                &format!("mwe[{}]", path.join(" ")),
+                &format!("token ID {} does not exist", index),
+            );
+        }
+
+        for (word, child) in node.children() {
+            path.push(word.to_string());
+            self.check_mwe(child, path);
+            path.pop();
+        }
+    }
+
+    /// Checks both the singular and plural token of every plural entry exist.
+    fn check_plurals(&mut self) {
+        for (singular, plural) in self.vocab.words.plural.iter() {
+            for id in [singular, plural] {
+                if !self.token_ids.contains(id) {
+                    self.add(
+                        VocabIssueKind::dangling_plural,
+                        &format!("plural[{}]", singular),
+                        &format!("token ID {} does not exist", id),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Checks the category indexes and nodes only reference existing categories.
+    fn check_categories(&mut self) {
+        let categories = &self.vocab.categories;
+        for (name, index) in [
+            ("nouns", &categories.nouns),
+            ("verbs", &categories.verbs),
+            ("adverbs", &categories.adverbs),
+            ("adjectives", &categories.adjectives),
+            ("ner", &categories.ner),
+        ] {
+            for (path, child) in index.children.iter() {
+                self.check_category_index(&format!("{}/{}", name, path), child);
+            }
+        }
+
+        for (category_id, node) in categories.nodes.iter() {
+            let location = format!("categories[{}]", category_id);
+            for id in node.fqn.iter().chain(node.children.values()) {
+                if !categories.nodes.contains_key(id) {
+                    self.add(
+                        VocabIssueKind::dangling_category,
+                        &location,
+                        &format!("category ID {} does not exist", id),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Checks a category index and all of its children point to existing categories.
+    fn check_category_index(&mut self, path: &str, index: &VocabCategoryIndex) {
+        if !self.vocab.categories.nodes.contains_key(&index.index) {
+            self.add(
+                VocabIssueKind::dangling_category,
+                &format!("categories[{}]", path),
+                &format!("category ID {} does not exist", index.index),
+            );
+        }
+
+        for (name, child) in index.children.iter() {
+            self.check_category_index(&format!("{}/{}", path, name), child);
+        }
+    }
+
+    /// Checks the pre-processing word lists only reference existing tokens.
+    fn check_preprocess(&mut self) {
+        let preprocess = &self.vocab.preprocess;
+        for (name, ids) in [
+            ("stop_words", &preprocess.stop_words),
+            ("predicative_verbs", &preprocess.predicative_verbs),
+            ("auxillary_verbs", &preprocess.auxillary_verbs),
+            ("infinitive_prefixes", &preprocess.infinitive_prefixes),
+        ] {
+            for id in ids.iter() {
+                self.check_id(&format!("preprocess.{}", name), *id);
+            }
+        }
+    }
+
+    /// Checks the POS models, capitalization and phrase intents only reference existing tokens.
+    fn check_words(&mut self) {
+        let words = &self.vocab.words;
+        for id in words.pos_tagger.words.keys() {
+            self.check_id("pos_tagger.words", *id);
+        }
+        for id in words.capitalization.keys() {
+            self.check_id("capitalization", *id);
+        }
+        self.check_phrase_intents(&words.phrase_intents, &mut Vec::new());
+    }
+
+    /// Checks every token ID within the phrase intents trie exists.
+    fn check_phrase_intents(&mut self, node: &PhraseIntents, path: &mut Vec<i32>) {
+        for (id, child) in node.children.iter() {
+            path.push(*id);
+            let location = path.iter().map(|id| id.to_string()).collect::<Vec<String>>();
+            self.check_id(&format!("phrase_intents[{}]", location.join(" ")), *id);
+            self.check_phrase_intents(child, path);
+            path.pop();
+        }
+    }
+
+    /// Adds a dangling reference issue if the token ID does not exist.
+    fn check_id(&mut self, location: &str, token_id: i32) {
+        if !self.token_ids.contains(&token_id) {
+            self.add(
+                VocabIssueKind::dangling_reference,
+                location,
+                &format!("token ID {} does not exist", token_id),
+            );
+        }
+    }
+
+    /// Adds an issue at the given location.
+    fn add(&mut self, kind: VocabIssueKind, location: &str, message: &str) {
+        self.issues.push(VocabIssue {
+            kind,
+            location: location.to_string(),
+            message: message.to_string(),
+        });
+    }
+}
+
+impl fmt::Display for VocabIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}